@@ -1,20 +1,27 @@
-use crate::config::AppConfig;
+use crate::config::{AppConfig, AuthMode};
 use crate::services::api::{
-    ApiClientConfig, AuthContext, FabrexClient, FabrexEndpoint, FabrexReassignmentResult,
-    FabrexUsage, GryfClient, SupernodeClient,
+    failed_request_curl, ApiClientConfig, AuthContext, BreakerStatus, CircuitBreaker, FabrexClient,
+    FabrexEndpoint, FabrexReassignmentResult, FabrexUsage, GryfClient, HttpCache, Reauthenticate,
+    RedfishClient, RequestMetrics, SupernodeClient,
+};
+use crate::services::auth::{
+    AuthError, CredentialDomain, CredentialKey, CredentialManager, CredentialSecret,
 };
-use crate::services::auth::{CredentialDomain, CredentialKey, CredentialManager, CredentialSecret};
 use crate::ui::{apply_theme, render_dashboard, DashboardSnapshot, DashboardState};
 use anyhow::{anyhow, Context, Result};
 use crossbeam_channel::{unbounded, Receiver, Sender, TryRecvError};
 use eframe::{egui, App, CreationContext, NativeOptions};
+use futures_util::future::BoxFuture;
+use reqwest::StatusCode;
+use std::collections::BTreeMap;
+use std::fmt;
 use std::sync::Arc;
 use std::thread;
 use std::time::{Duration, SystemTime};
 use tokio::runtime::Runtime;
-use tokio::sync::oneshot;
+use tokio::sync::{oneshot, Mutex as AsyncMutex};
 use tokio::time;
-use tokio::try_join;
+use tokio::{join, try_join};
 
 pub fn run(config: AppConfig) -> Result<()> {
     let shared_config = Arc::new(config);
@@ -39,6 +46,7 @@ pub fn run(config: AppConfig) -> Result<()> {
 struct FabreXLensApp {
     config: Arc<AppConfig>,
     credential_manager: Arc<CredentialManager>,
+    services: ServiceContext,
     dashboard_state: DashboardState,
     command_tx: Sender<AppCommand>,
     event_rx: Receiver<AppEvent>,
@@ -64,12 +72,9 @@ impl FabreXLensApp {
         let (command_tx, command_rx) = unbounded();
         let (event_tx, event_rx) = unbounded();
 
-        spawn_background_worker(
-            config.clone(),
-            credential_manager.clone(),
-            command_rx,
-            event_tx,
-        );
+        let services = ServiceContext::new(config.clone(), credential_manager.clone())
+            .with_events(event_tx.clone());
+        spawn_background_worker(services.clone(), command_rx, event_tx);
 
         let dark_mode = false;
         apply_theme(&cc.egui_ctx, dark_mode);
@@ -78,6 +83,7 @@ impl FabreXLensApp {
         let mut app = Self {
             config,
             credential_manager,
+            services,
             dashboard_state: DashboardState::new(),
             command_tx,
             event_rx,
@@ -99,14 +105,13 @@ impl FabreXLensApp {
     }
 
     fn refresh_missing_credentials(&mut self) {
-        let domains = [
-            CredentialDomain::FabreX,
-            CredentialDomain::Gryf,
-            CredentialDomain::Supernode,
-            CredentialDomain::Redfish,
-        ];
-
         let mut missing = Vec::new();
+        // Replayed requests are answered without credentials.
+        let domains = if self.config.is_replaying() {
+            Vec::new()
+        } else {
+            CredentialDomain::ALL.to_vec()
+        };
         for domain in domains {
             let key = CredentialKey::default(domain.clone());
             match self.credential_manager.has_credentials(&key) {
@@ -244,7 +249,17 @@ impl FabreXLensApp {
     }
 
     fn push_log(&mut self, level: LogLevel, message: impl Into<String>) {
-        let entry = LogEntry::new(level, message.into());
+        self.push_log_entry(LogEntry::new(level, message.into()));
+    }
+
+    /// Logs a failed operation, keeping the failing request so it can be copied as `curl`.
+    fn push_failure(&mut self, message: String, failure: &Failure) {
+        let mut entry = LogEntry::new(LogLevel::Error, message);
+        entry.curl = failure.curl.clone();
+        self.push_log_entry(entry);
+    }
+
+    fn push_log_entry(&mut self, entry: LogEntry) {
         self.telemetry_log.push(entry);
         const MAX_LOG_ENTRIES: usize = 200;
         if self.telemetry_log.len() > MAX_LOG_ENTRIES {
@@ -303,6 +318,15 @@ impl FabreXLensApp {
                         ui.label(
                             egui::RichText::new(&entry.message).text_style(egui::TextStyle::Body),
                         );
+                        if let Some(curl) = &entry.curl {
+                            if ui
+                                .small_button("Copy as curl")
+                                .on_hover_text("Copy the failed request with secrets redacted")
+                                .clicked()
+                            {
+                                ui.ctx().copy_text(curl.clone());
+                            }
+                        }
                     });
                 });
             }
@@ -340,13 +364,11 @@ impl FabreXLensApp {
                 self.status_message = Some("Telemetry updated successfully.".into());
                 self.push_log(LogLevel::Info, "Telemetry updated successfully.");
             }
-            AppEvent::DashboardFailed(error) => {
+            AppEvent::DashboardFailed(failure) => {
+                let error = &failure.message;
                 self.dashboard_state.set_error(error.clone());
                 self.status_message = Some(format!("Dashboard refresh failed: {error}"));
-                self.push_log(
-                    LogLevel::Error,
-                    format!("Dashboard refresh failed: {error}"),
-                );
+                self.push_failure(format!("Dashboard refresh failed: {error}"), &failure);
             }
             AppEvent::ReassignmentCompleted(result) => {
                 self.reassignment_form.on_success(&result);
@@ -362,10 +384,31 @@ impl FabreXLensApp {
                     ),
                 );
             }
-            AppEvent::ReassignmentFailed(error) => {
-                self.reassignment_form.on_failure(&error);
+            AppEvent::ReassignmentFailed(failure) => {
+                let error = &failure.message;
+                self.reassignment_form.on_failure(error);
                 self.status_message = Some(format!("Reassignment failed: {error}"));
-                self.push_log(LogLevel::Error, format!("Reassignment failed: {error}"));
+                self.push_failure(format!("Reassignment failed: {error}"), &failure);
+            }
+            AppEvent::Reauthenticated {
+                domain,
+                status,
+                error,
+            } => {
+                let rejected = format!(
+                    "{domain} rejected the current credentials (HTTP {})",
+                    status.as_u16()
+                );
+                match error {
+                    None => self.push_log(
+                        LogLevel::Info,
+                        format!("{rejected}; re-authenticated and retried"),
+                    ),
+                    Some(error) => self.push_log(
+                        LogLevel::Warn,
+                        format!("{rejected}; re-authentication failed: {error}"),
+                    ),
+                }
             }
         }
     }
@@ -374,7 +417,12 @@ impl FabreXLensApp {
         ui.horizontal(|ui| {
             ui.heading(&self.config.application_name);
             ui.separator();
-            ui.label(format!("FabreX: {}", self.config.fabrex_base_url));
+            ui.label(format!(
+                "FabreX: {}",
+                self.config
+                    .base_url(&CredentialDomain::FabreX)
+                    .unwrap_or_default()
+            ));
             if ui.button("Refresh now").clicked() {
                 self.request_refresh();
             }
@@ -424,6 +472,8 @@ impl FabreXLensApp {
             }
         });
 
+        self.render_service_health(ctx, ui);
+
         if let Some(message) = &self.status_message {
             ui.label(message);
         }
@@ -449,6 +499,21 @@ impl FabreXLensApp {
             );
         }
 
+        let insecure = self.config.tls.insecure_domains();
+        if !insecure.is_empty() {
+            ui.colored_label(
+                egui::Color32::LIGHT_RED,
+                format!(
+                    "TLS certificate verification disabled for: {}",
+                    insecure
+                        .iter()
+                        .map(|d| d.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            );
+        }
+
         if self.worker_failed {
             ui.colored_label(
                 egui::Color32::LIGHT_RED,
@@ -457,6 +522,31 @@ impl FabreXLensApp {
         }
     }
 
+    /// One indicator per service showing its circuit breaker state.
+    fn render_service_health(&self, ctx: &egui::Context, ui: &mut egui::Ui) {
+        let statuses = self.services.breaker_statuses();
+        ui.horizontal(|ui| {
+            ui.label("Services:");
+            for (domain, status) in &statuses {
+                let color = match status {
+                    BreakerStatus::Closed => egui::Color32::from_rgb(33, 150, 83),
+                    BreakerStatus::HalfOpen => egui::Color32::from_rgb(236, 146, 36),
+                    BreakerStatus::Open { .. } => egui::Color32::from_rgb(225, 85, 73),
+                };
+                ui.colored_label(color, format!("● {domain}"))
+                    .on_hover_text(format!("{domain}: {status}"));
+            }
+        });
+
+        // Keep the countdown and the half-open transition current while a circuit is open.
+        if statuses
+            .iter()
+            .any(|(_, status)| *status != BreakerStatus::Closed)
+        {
+            ctx.request_repaint_after(Duration::from_secs(1));
+        }
+    }
+
     fn render_credentials_help(&mut self, ui: &mut egui::Ui) {
         let frame = egui::Frame::group(ui.style())
             .fill(ui.visuals().extreme_bg_color)
@@ -488,7 +578,7 @@ impl FabreXLensApp {
                         )
                         .clicked()
                     {
-                        self.provision_form = Some(ProvisionForm::new(domain.clone()));
+                        self.provision_form = Some(ProvisionForm::new(domain.clone(), self.config.auth_mode(domain) == AuthMode::Oauth2));
                     }
                 });
             }
@@ -566,7 +656,10 @@ impl FabreXLensApp {
                             LogLevel::Error,
                             format!("Failed to store {domain} credentials: {err}"),
                         );
-                        let mut retry = ProvisionForm::new(domain.clone());
+                        let mut retry = ProvisionForm::new(
+                            domain.clone(),
+                            self.config.auth_mode(&domain) == AuthMode::Oauth2,
+                        );
                         retry.username = secret.username.clone();
                         retry.password = secret.password.clone();
                         retry.api_token = secret.api_token.clone().unwrap_or_default();
@@ -811,6 +904,8 @@ struct LogEntry {
     timestamp: SystemTime,
     level: LogLevel,
     message: String,
+    /// Redacted `curl` command for the request behind a failure, when there was one.
+    curl: Option<String>,
 }
 
 impl LogEntry {
@@ -819,6 +914,7 @@ impl LogEntry {
             timestamp: SystemTime::now(),
             level,
             message,
+            curl: None,
         }
     }
 
@@ -873,20 +969,39 @@ enum AppCommand {
 
 enum AppEvent {
     DashboardUpdated(DashboardSnapshot),
-    DashboardFailed(String),
+    DashboardFailed(Failure),
     ReassignmentCompleted(FabrexReassignmentResult),
-    ReassignmentFailed(String),
+    ReassignmentFailed(Failure),
+    /// A service rejected its credentials and fresh ones were requested.
+    Reauthenticated {
+        domain: CredentialDomain,
+        status: StatusCode,
+        error: Option<String>,
+    },
+}
+
+/// A background failure as shown in the UI.
+struct Failure {
+    message: String,
+    curl: Option<String>,
+}
+
+impl From<anyhow::Error> for Failure {
+    fn from(err: anyhow::Error) -> Self {
+        Self {
+            message: format!("{err:#}"),
+            curl: failed_request_curl(&err),
+        }
+    }
 }
 
 fn spawn_background_worker(
-    config: Arc<AppConfig>,
-    credential_manager: Arc<CredentialManager>,
+    services: ServiceContext,
     command_rx: Receiver<AppCommand>,
     event_tx: Sender<AppEvent>,
 ) {
     thread::spawn(move || {
         let runtime = Runtime::new().expect("tokio runtime");
-        let services = ServiceContext::new(config, credential_manager);
         let mut poller: Option<PollingHandle> = None;
 
         while let Ok(command) = command_rx.recv() {
@@ -898,7 +1013,7 @@ fn spawn_background_worker(
                             let _ = event_tx.send(AppEvent::DashboardUpdated(snapshot));
                         }
                         Err(err) => {
-                            let _ = event_tx.send(AppEvent::DashboardFailed(err.to_string()));
+                            let _ = event_tx.send(AppEvent::DashboardFailed(err.into()));
                         }
                     }
                 }
@@ -918,7 +1033,7 @@ fn spawn_background_worker(
                             let _ = event_tx.send(AppEvent::ReassignmentCompleted(res));
                         }
                         Err(err) => {
-                            let _ = event_tx.send(AppEvent::ReassignmentFailed(err.to_string()));
+                            let _ = event_tx.send(AppEvent::ReassignmentFailed(err.into()));
                         }
                     }
                 }
@@ -983,7 +1098,7 @@ fn start_polling(
                 _ = ticker.tick() => {
                     match fetch_dashboard_snapshot(&services).await {
                         Ok(snapshot) => { let _ = event_tx.send(AppEvent::DashboardUpdated(snapshot)); }
-                        Err(err) => { let _ = event_tx.send(AppEvent::DashboardFailed(err.to_string())); }
+                        Err(err) => { let _ = event_tx.send(AppEvent::DashboardFailed(err.into())); }
                     }
                 }
                 _ = &mut stop_rx => break,
@@ -995,59 +1110,247 @@ fn start_polling(
 }
 
 #[derive(Clone)]
-struct ServiceContext {
+pub(crate) struct ServiceContext {
     config: Arc<AppConfig>,
     credentials: Arc<CredentialManager>,
+    /// Shared by every client so polls can revalidate instead of re-downloading.
+    cache: Arc<HttpCache>,
+    breakers: Arc<BTreeMap<CredentialDomain, Arc<CircuitBreaker>>>,
+    /// Held while a service's OAuth2 token is renewed, so concurrent rejections share one
+    /// exchange.
+    renewals: Arc<BTreeMap<CredentialDomain, Arc<AsyncMutex<()>>>>,
+    metrics: Arc<RequestMetrics>,
+    /// Receives re-authentication notices for the GUI event log.
+    events: Option<Sender<AppEvent>>,
 }
 
 impl ServiceContext {
-    fn new(config: Arc<AppConfig>, credentials: Arc<CredentialManager>) -> Self {
+    /// Services the dashboard reaches, each with its own breaker. Redfish is only probed
+    /// through its service root, and only when `redfish_base_url` is set.
+    const DOMAINS: [CredentialDomain; 4] = [
+        CredentialDomain::FabreX,
+        CredentialDomain::Gryf,
+        CredentialDomain::Supernode,
+        CredentialDomain::Redfish,
+    ];
+
+    pub(crate) fn new(config: Arc<AppConfig>, credentials: Arc<CredentialManager>) -> Self {
+        let policy = config.circuit_breaker.policy();
+        let breakers = Self::DOMAINS
+            .into_iter()
+            .map(|domain| (domain, Arc::new(CircuitBreaker::new(policy))))
+            .collect();
+        let renewals = Self::DOMAINS
+            .into_iter()
+            .map(|domain| (domain, Arc::default()))
+            .collect();
         Self {
             config,
             credentials,
+            cache: Arc::new(HttpCache::new()),
+            breakers: Arc::new(breakers),
+            renewals: Arc::new(renewals),
+            metrics: Arc::new(RequestMetrics::new()),
+            events: None,
+        }
+    }
+
+    fn with_events(mut self, events: Sender<AppEvent>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    fn api_config(&self, domain: &CredentialDomain) -> Result<ApiClientConfig> {
+        let mut config = self
+            .config
+            .api_config(domain)?
+            .with_cache(self.cache.clone())
+            .with_metrics(self.metrics.for_service(domain.to_string()));
+        if let Some(breaker) = self.breakers.get(domain) {
+            config = config.with_breaker(breaker.clone());
+        }
+        // Replayed requests carry no credentials to renew.
+        let renewable = !self.config.is_replaying();
+        if let Some(renewal) = self.renewals.get(domain).filter(|_| renewable) {
+            config = config.with_reauth(Arc::new(ServiceReauth {
+                services: self.clone(),
+                domain: domain.clone(),
+                renewal: renewal.clone(),
+            }));
+        }
+        Ok(config)
+    }
+
+    /// Circuit breaker state of every service with a configured base URL.
+    pub(crate) fn breaker_statuses(&self) -> Vec<(CredentialDomain, BreakerStatus)> {
+        self.breakers
+            .iter()
+            .filter(|(domain, _)| self.config.base_url(domain).is_some())
+            .map(|(domain, breaker)| (domain.clone(), breaker.status()))
+            .collect()
+    }
+
+    /// Stored credentials for `domain`; replayed requests need none, so the keyring is skipped.
+    async fn auth_context(&self, domain: CredentialDomain) -> Result<AuthContext> {
+        if self.config.is_replaying() {
+            return Ok(AuthContext::default());
         }
+        service_auth(
+            &self.config,
+            &self.credentials,
+            &CredentialKey::default(domain),
+        )
+        .await
     }
 
-    fn auth_context(&self, domain: CredentialDomain) -> Result<AuthContext> {
-        let key = CredentialKey::default(domain.clone());
-        self.credentials
-            .auth_context(&key)?
-            .ok_or_else(|| anyhow!("Missing credentials for {domain}"))
+    fn report_reauthentication(
+        &self,
+        domain: &CredentialDomain,
+        status: StatusCode,
+        error: Option<&anyhow::Error>,
+    ) {
+        match error {
+            None => tracing::info!(%domain, status = status.as_u16(), "Re-authenticated"),
+            Some(err) => {
+                tracing::warn!(%domain, status = status.as_u16(), "Re-authentication failed: {err:#}")
+            }
+        }
+        if let Some(events) = &self.events {
+            let _ = events.send(AppEvent::Reauthenticated {
+                domain: domain.clone(),
+                status,
+                error: error.map(|err| format!("{err:#}")),
+            });
+        }
     }
 
-    fn fabrex_client(&self) -> Result<FabrexClient> {
-        let auth = self.auth_context(CredentialDomain::FabreX)?;
-        let config = ApiClientConfig::try_from_url(&self.config.fabrex_base_url)?;
+    pub(crate) async fn fabrex_client(&self) -> Result<FabrexClient> {
+        let auth = self.auth_context(CredentialDomain::FabreX).await?;
+        let config = self.api_config(&CredentialDomain::FabreX)?;
         Ok(FabrexClient::new(config)?.with_auth(auth))
     }
 
-    fn gryf_client(&self) -> Result<GryfClient> {
-        let auth = self.auth_context(CredentialDomain::Gryf)?;
-        let config = ApiClientConfig::try_from_url(&self.config.gryf_base_url)?;
+    pub(crate) async fn gryf_client(&self) -> Result<GryfClient> {
+        let auth = self.auth_context(CredentialDomain::Gryf).await?;
+        let config = self.api_config(&CredentialDomain::Gryf)?;
         Ok(GryfClient::new(config)?.with_auth(auth))
     }
 
-    fn supernode_client(&self) -> Result<SupernodeClient> {
-        let auth = self.auth_context(CredentialDomain::Supernode)?;
-        let config = ApiClientConfig::try_from_url(&self.config.supernode_base_url)?;
+    /// Unauthenticated Redfish client; the dashboard only reads the BMC's service root.
+    pub(crate) fn redfish_client(&self) -> Result<RedfishClient> {
+        let config = self.api_config(&CredentialDomain::Redfish)?;
+        Ok(RedfishClient::new(config)?)
+    }
+
+    pub(crate) async fn supernode_client(&self) -> Result<SupernodeClient> {
+        let auth = self.auth_context(CredentialDomain::Supernode).await?;
+        let config = self.api_config(&CredentialDomain::Supernode)?;
         Ok(SupernodeClient::new(config)?.with_auth(auth))
     }
 }
 
-async fn fetch_dashboard_snapshot(services: &ServiceContext) -> Result<DashboardSnapshot> {
-    let fabrex_client = services.fabrex_client()?;
-    let gryf_client = services.gryf_client()?;
-    let supernode_client = services.supernode_client()?;
+/// Auth for requests to the service of `key`.
+///
+/// Domains using OAuth2 get a cached access token, exchanged for the stored client id and
+/// secret whenever the cached one is missing or about to expire; the others use the stored
+/// credentials directly.
+pub(crate) async fn service_auth(
+    config: &AppConfig,
+    credentials: &CredentialManager,
+    key: &CredentialKey,
+) -> Result<AuthContext> {
+    if let Some(grant) = config.client_credentials(key.domain())? {
+        let endpoint = config.token_endpoint_config(key.domain(), &grant)?;
+        return grant
+            .authorize(credentials, key, endpoint)
+            .await
+            .with_context(|| format!("Requesting an OAuth2 token for {key}"));
+    }
+    credentials
+        .auth_context(key)?
+        .ok_or_else(|| AuthError::MissingCredentials(key.to_string()).into())
+}
+
+/// Replaces the credentials of one service after it rejects them: OAuth2 services evict the
+/// cached token and exchange a new one, the others re-read the stored secret and retry only if
+/// it changed.
+struct ServiceReauth {
+    services: ServiceContext,
+    domain: CredentialDomain,
+    renewal: Arc<AsyncMutex<()>>,
+}
+
+impl fmt::Debug for ServiceReauth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ServiceReauth")
+            .field("domain", &self.domain)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Reauthenticate for ServiceReauth {
+    fn reauthenticate<'a>(
+        &'a self,
+        status: StatusCode,
+        rejected: &'a AuthContext,
+    ) -> BoxFuture<'a, Option<AuthContext>> {
+        Box::pin(async move {
+            let _renewal = self.renewal.lock().await;
+            let key = CredentialKey::default(self.domain.clone());
+            if self.services.config.auth_mode(&self.domain) != AuthMode::Oauth2 {
+                // The stored secret may have been rotated since the client was built; sending
+                // the rejected one again would only be rejected again.
+                let stored = self.services.auth_context(self.domain.clone()).await;
+                return match stored {
+                    Ok(auth) if auth == *rejected => None,
+                    result => {
+                        self.services.report_reauthentication(
+                            &self.domain,
+                            status,
+                            result.as_ref().err(),
+                        );
+                        result.ok()
+                    }
+                };
+            }
+            // Another request rejected with the same token may have renewed it while this one
+            // waited for the lock.
+            if let Some(current) = self.services.credentials.cached_token(&key) {
+                if rejected.bearer_token.as_deref() != Some(current.as_str()) {
+                    return Some(AuthContext::bearer(current));
+                }
+            }
+            self.services.credentials.evict_token(&key);
+            let result = self.services.auth_context(self.domain.clone()).await;
+            self.services
+                .report_reauthentication(&self.domain, status, result.as_ref().err());
+            result.ok()
+        })
+    }
+}
+
+pub(crate) async fn fetch_dashboard_snapshot(
+    services: &ServiceContext,
+) -> Result<DashboardSnapshot> {
+    let fabrex_client = services.fabrex_client().await?;
+    let gryf_client = services.gryf_client().await?;
+    let supernode_client = services.supernode_client().await?;
 
     let fabrex_for_join = fabrex_client.clone();
     let gryf_for_join = gryf_client.clone();
     let supernode_for_join = supernode_client.clone();
 
-    let (fabrics, workloads, supernodes) = try_join!(
-        fabrex_for_join.list_fabrics(),
-        gryf_for_join.list_workloads(),
-        supernode_for_join.list_nodes()
-    )?;
+    let (lists, ()) = join!(
+        async {
+            try_join!(
+                fabrex_for_join.list_fabrics(),
+                gryf_for_join.list_workloads(),
+                supernode_for_join.list_nodes()
+            )
+        },
+        probe_redfish(services)
+    );
+    let (fabrics, workloads, supernodes) = lists?;
 
     let mut usage: Vec<FabrexUsage> = Vec::new();
     let mut endpoints: Vec<FabrexEndpoint> = Vec::new();
@@ -1061,18 +1364,17 @@ async fn fetch_dashboard_snapshot(services: &ServiceContext) -> Result<Dashboard
             .with_context(|| format!("Fetching usage for fabric {fabric_id}"))?;
         usage.push(fabric_usage);
 
-        let mut endpoint_page = fabrex_client
+        let mut fabric_endpoints = fabrex_client
             .clone()
-            .list_endpoints(&fabric_id, None)
+            .list_endpoints(&fabric_id)
             .await
-            .with_context(|| format!("Fetching endpoints for fabric {fabric_id}"))?
-            .items;
-        for endpoint in &mut endpoint_page {
+            .with_context(|| format!("Fetching endpoints for fabric {fabric_id}"))?;
+        for endpoint in &mut fabric_endpoints {
             if endpoint.fabric_id.is_none() {
                 endpoint.fabric_id = Some(fabric_id.clone());
             }
         }
-        endpoints.extend(endpoint_page);
+        endpoints.extend(fabric_endpoints);
     }
 
     let alerts = usage
@@ -1088,16 +1390,34 @@ async fn fetch_dashboard_snapshot(services: &ServiceContext) -> Result<Dashboard
         supernodes,
         endpoints,
         alerts,
+        transfer: services.cache.take_stats(),
+        backend: services.metrics.snapshot(),
     })
 }
 
+/// Reads the BMC's service root so the Redfish breaker, and its top-bar indicator, follow the
+/// BMC's health. A failed probe shows up there only; it does not fail the refresh.
+async fn probe_redfish(services: &ServiceContext) {
+    if services
+        .config
+        .base_url(&CredentialDomain::Redfish)
+        .is_none()
+    {
+        return;
+    }
+    let probe = async { Ok::<_, anyhow::Error>(services.redfish_client()?.service_root().await?) };
+    if let Err(err) = probe.await {
+        tracing::debug!("Redfish service root probe failed: {err:#}");
+    }
+}
+
 async fn perform_reassignment(
     services: &ServiceContext,
     fabric_id: String,
     endpoint_id: String,
     target_supernode: String,
 ) -> Result<FabrexReassignmentResult> {
-    let client = services.fabrex_client()?;
+    let client = services.fabrex_client().await?;
     let result = client
         .reassign_endpoint(&fabric_id, &endpoint_id, &target_supernode)
         .await?;
@@ -1107,6 +1427,8 @@ async fn perform_reassignment(
 #[derive(Debug, Clone)]
 struct ProvisionForm {
     domain: CredentialDomain,
+    /// The service exchanges a client id and secret for OAuth2 tokens; no API token is asked for.
+    oauth: bool,
     username: String,
     password: String,
     api_token: String,
@@ -1116,9 +1438,10 @@ struct ProvisionForm {
 }
 
 impl ProvisionForm {
-    fn new(domain: CredentialDomain) -> Self {
+    fn new(domain: CredentialDomain, oauth: bool) -> Self {
         Self {
             domain,
+            oauth,
             username: String::new(),
             password: String::new(),
             api_token: String::new(),
@@ -1143,36 +1466,59 @@ impl ProvisionForm {
         );
 
         ui.separator();
-        ui.label(egui::RichText::new("Username").strong());
+        let (username_label, username_hint, password_label, password_hint) = if self.oauth {
+            (
+                "Client ID",
+                "OAuth2 client id",
+                "Client secret",
+                "client secret",
+            )
+        } else {
+            (
+                "Username",
+                "service account username",
+                "Password",
+                "password",
+            )
+        };
+        ui.label(egui::RichText::new(username_label).strong());
         ui.add(
             egui::TextEdit::singleline(&mut self.username)
-                .hint_text("service account username")
+                .hint_text(username_hint)
                 .min_size(egui::vec2(ui.available_width(), 0.0)),
         );
 
-        ui.label(egui::RichText::new("Password").strong());
+        ui.label(egui::RichText::new(password_label).strong());
         ui.horizontal(|ui| {
             let password_edit = egui::TextEdit::singleline(&mut self.password)
-                .hint_text("password")
+                .hint_text(password_hint)
                 .password(!self.show_password)
                 .min_size(egui::vec2(ui.available_width() - 80.0, 0.0));
             ui.add(password_edit);
             ui.toggle_value(&mut self.show_password, "Show");
         });
 
-        ui.label(
-            egui::RichText::new("API token (optional)")
-                .text_style(egui::TextStyle::Small)
-                .color(egui::Color32::from_rgb(120, 130, 150)),
-        );
-        ui.horizontal(|ui| {
-            let token_edit = egui::TextEdit::singleline(&mut self.api_token)
-                .hint_text("token or leave blank")
-                .password(!self.show_token)
-                .min_size(egui::vec2(ui.available_width() - 80.0, 0.0));
-            ui.add(token_edit);
-            ui.toggle_value(&mut self.show_token, "Show");
-        });
+        if self.oauth {
+            ui.label(
+                egui::RichText::new("Access tokens are requested and renewed automatically.")
+                    .text_style(egui::TextStyle::Small)
+                    .color(egui::Color32::from_rgb(120, 130, 150)),
+            );
+        } else {
+            ui.label(
+                egui::RichText::new("API token (optional)")
+                    .text_style(egui::TextStyle::Small)
+                    .color(egui::Color32::from_rgb(120, 130, 150)),
+            );
+            ui.horizontal(|ui| {
+                let token_edit = egui::TextEdit::singleline(&mut self.api_token)
+                    .hint_text("token or leave blank")
+                    .password(!self.show_token)
+                    .min_size(egui::vec2(ui.available_width() - 80.0, 0.0));
+                ui.add(token_edit);
+                ui.toggle_value(&mut self.show_token, "Show");
+            });
+        }
 
         if let Some(error) = &self.error {
             ui.colored_label(egui::Color32::from_rgb(225, 85, 73), error);
@@ -1182,7 +1528,8 @@ impl ProvisionForm {
         let mut event = ProvisionUiEvent::None;
         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
             let has_username = !self.username.trim().is_empty();
-            let has_secret = !self.password.trim().is_empty() || !self.api_token.trim().is_empty();
+            let has_secret = !self.password.trim().is_empty()
+                || (!self.oauth && !self.api_token.trim().is_empty());
             let can_submit = has_username && has_secret;
             let save_clicked = ui
                 .add_enabled(
@@ -1211,6 +1558,8 @@ impl ProvisionForm {
                         },
                     };
                     event = ProvisionUiEvent::Submit(secret);
+                } else if self.oauth {
+                    self.error = Some("Client ID and client secret are required.".into());
                 } else {
                     self.error =
                         Some("Username and either a password or API token are required.".into());
@@ -1,5 +1,7 @@
+use crate::config::AppConfig;
 use crate::services::auth::CredentialDomain;
 use clap::{Parser, Subcommand, ValueEnum};
+use clap_complete::engine::{ArgValueCandidates, CompletionCandidate};
 use std::path::PathBuf;
 
 /// Command line interface for FabreXLens.
@@ -16,13 +18,41 @@ pub struct Cli {
     pub config: Option<PathBuf>,
 
     /// Named profile to load (e.g. dev, staging, prod).
-    #[arg(short, long)]
+    #[arg(short, long, add = ArgValueCandidates::new(profile_candidates))]
     pub profile: Option<String>,
 
     /// Launch without opening the UI (useful for scripting and diagnostics).
     #[arg(long)]
     pub headless: bool,
 
+    /// In headless mode, take a single snapshot and exit.
+    #[arg(long, requires = "headless")]
+    pub once: bool,
+
+    /// How to report a failure on stderr.
+    #[arg(long, value_enum, default_value_t = ErrorFormat::Text, global = true)]
+    pub error_format: ErrorFormat,
+
+    /// Log each HTTP request (method, URL, status, latency, queueing) to stderr with secrets
+    /// redacted; `--trace-http=bodies` also logs request and response bodies.
+    #[arg(
+        long,
+        value_enum,
+        num_args = 0..=1,
+        default_missing_value = "summary",
+        require_equals = true,
+        global = true
+    )]
+    pub trace_http: Option<HttpTrace>,
+
+    /// Record every HTTP exchange, with secrets redacted, to `<DIR>/cassette.jsonl`.
+    #[arg(long, value_name = "DIR", global = true, conflicts_with = "replay")]
+    pub record: Option<PathBuf>,
+
+    /// Answer HTTP requests from `<DIR>/cassette.jsonl` without any network access.
+    #[arg(long, value_name = "DIR", global = true)]
+    pub replay: Option<PathBuf>,
+
     #[command(subcommand)]
     pub command: Option<Command>,
 }
@@ -36,6 +66,236 @@ pub enum Command {
         #[arg(short, long, default_value = "default")]
         scope: String,
     },
+    /// List, rotate, delete, and test stored credentials.
+    Auth {
+        #[command(subcommand)]
+        command: AuthCommand,
+        /// Output format for command results.
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table, global = true)]
+        output: OutputFormat,
+    },
+    /// Query FabreX fabrics, usage, and endpoints.
+    Fabrics {
+        #[command(subcommand)]
+        command: FabricsCommand,
+        /// Output format for command results.
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table, global = true)]
+        output: OutputFormat,
+    },
+    /// Inspect and reassign Gryf workloads.
+    Workloads {
+        #[command(subcommand)]
+        command: WorkloadsCommand,
+        /// Output format for command results.
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table, global = true)]
+        output: OutputFormat,
+    },
+    /// Inspect supernodes and invoke node actions.
+    Nodes {
+        #[command(subcommand)]
+        command: NodesCommand,
+        /// Output format for command results.
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table, global = true)]
+        output: OutputFormat,
+    },
+    /// Reassign FabreX endpoints between supernodes.
+    Endpoints {
+        #[command(subcommand)]
+        command: EndpointsCommand,
+        /// Output format for command results.
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table, global = true)]
+        output: OutputFormat,
+    },
+    /// Check keyring access, credentials, DNS, TLS, and authenticated access for every service.
+    Doctor {
+        /// Output format for the report.
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        output: OutputFormat,
+    },
+    /// Print a shell completion script for FabreXLens.
+    Completions {
+        #[arg(value_enum)]
+        shell: CompletionShell,
+    },
+    /// Print the FabreXLens man page in roff format.
+    Manpage,
+    /// Inspect and check the effective configuration.
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+        /// Output format for command results.
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table, global = true)]
+        output: OutputFormat,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ConfigCommand {
+    /// Print the effective configuration and where each value came from.
+    Show,
+    /// Check base URLs and the poll interval; exits non-zero on problems.
+    Validate,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum AuthCommand {
+    /// List every domain and scope with stored credentials.
+    List,
+    /// Remove stored credentials.
+    Delete {
+        #[arg(value_enum)]
+        domain: CredentialDomainArg,
+        #[arg(short, long, default_value = "default")]
+        scope: String,
+        /// Skip the confirmation prompt.
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Replace stored credentials with newly entered ones.
+    Rotate {
+        #[arg(value_enum)]
+        domain: CredentialDomainArg,
+        #[arg(short, long, default_value = "default")]
+        scope: String,
+    },
+    /// Make an authenticated call with stored credentials and report the outcome.
+    Test {
+        #[arg(value_enum)]
+        domain: CredentialDomainArg,
+        #[arg(short, long, default_value = "default")]
+        scope: String,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum FabricsCommand {
+    /// List all managed fabrics.
+    List,
+    /// Show a single fabric.
+    Show { fabric_id: String },
+    /// Show utilization and alerts for a fabric.
+    Usage { fabric_id: String },
+    /// List the endpoints attached to a fabric.
+    Endpoints { fabric_id: String },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum EndpointsCommand {
+    /// Attach an endpoint to a different supernode.
+    Reassign {
+        /// Fabric that owns the endpoint.
+        #[arg(long)]
+        fabric: String,
+        /// Endpoint to move.
+        #[arg(long)]
+        endpoint: String,
+        /// Target supernode identifier.
+        #[arg(long)]
+        to: String,
+        /// Validate the fabric, endpoint, and supernode without submitting anything.
+        #[arg(long, conflicts_with = "wait")]
+        dry_run: bool,
+        /// Poll until the endpoint reports the new attachment.
+        #[arg(long)]
+        wait: bool,
+        /// Seconds to wait for the attachment before giving up.
+        #[arg(long, default_value_t = 300, requires = "wait")]
+        timeout: u64,
+        /// Seconds between attachment checks while waiting.
+        #[arg(long, default_value_t = 5, requires = "wait")]
+        poll_interval: u64,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum WorkloadsCommand {
+    /// List workloads, optionally filtered by state or owner.
+    List {
+        /// Only show workloads in this state (case-insensitive).
+        #[arg(long)]
+        state: Option<String>,
+        /// Only show workloads owned by this principal (case-insensitive).
+        #[arg(long)]
+        owner: Option<String>,
+    },
+    /// Show a workload with its tasks and metrics.
+    Show { workload_id: String },
+    /// Move a workload to another fabric.
+    Reassign {
+        workload_id: String,
+        /// Target fabric identifier.
+        #[arg(long)]
+        fabric: String,
+        /// Reason recorded with the reassignment request.
+        #[arg(long)]
+        reason: String,
+        /// Skip the confirmation prompt.
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum NodesCommand {
+    /// List all supernodes.
+    List,
+    /// Show CPU, memory, and open issues for one node or every node.
+    Health {
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        node_id: Option<String>,
+        /// Report health for every node.
+        #[arg(long)]
+        all: bool,
+    },
+    /// Invoke an action (e.g. restart) on a node.
+    Action {
+        node_id: String,
+        action: String,
+        /// JSON payload file, or `-` to read it from stdin.
+        #[arg(long)]
+        payload: Option<PathBuf>,
+        /// Skip the confirmation prompt for disruptive actions.
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Table,
+    Json,
+    Yaml,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ErrorFormat {
+    Text,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum HttpTrace {
+    Summary,
+    Bodies,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+    Powershell,
+}
+
+impl CompletionShell {
+    pub fn name(self) -> &'static str {
+        match self {
+            CompletionShell::Bash => "bash",
+            CompletionShell::Zsh => "zsh",
+            CompletionShell::Fish => "fish",
+            CompletionShell::Powershell => "powershell",
+        }
+    }
 }
 
 #[derive(Debug, Clone, ValueEnum)]
@@ -56,3 +316,11 @@ impl From<CredentialDomainArg> for CredentialDomain {
         }
     }
 }
+
+/// Offers the profiles that have a `fabrexlens.<name>.toml` file in the config directory.
+fn profile_candidates() -> Vec<CompletionCandidate> {
+    AppConfig::available_profiles()
+        .into_iter()
+        .map(CompletionCandidate::new)
+        .collect()
+}
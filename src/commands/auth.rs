@@ -0,0 +1,158 @@
+use super::output::{self, emit, Table};
+use super::{block_on, confirm};
+use crate::app::service_auth;
+use crate::cli::{AuthCommand, OutputFormat};
+use crate::config::AppConfig;
+use crate::services::api::{
+    ApiClientConfig, AuthContext, FabrexClient, GryfClient, RedfishClient, RedfishSessionManager,
+    SupernodeClient,
+};
+use crate::services::auth::{
+    prompt_for_credentials, AuthError, CredentialDomain, CredentialKey, CredentialManager,
+};
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::process::ExitCode;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredCredential {
+    domain: String,
+    scope: String,
+    summary: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthTestReport {
+    domain: String,
+    scope: String,
+    base_url: String,
+    authenticated: bool,
+}
+
+pub fn run(config: AppConfig, command: AuthCommand, format: OutputFormat) -> Result<ExitCode> {
+    let manager = CredentialManager::with_default_keyring();
+
+    match command {
+        AuthCommand::List => {
+            let stored: Vec<StoredCredential> = manager
+                .list_credentials()
+                .context("Enumerating stored credentials")?
+                .into_iter()
+                .map(|(key, secret)| StoredCredential {
+                    domain: key.domain().to_string(),
+                    scope: key.scope().to_string(),
+                    summary: secret.redacted_summary(),
+                })
+                .collect();
+            emit(format, &stored, || {
+                let mut table = Table::new(&["DOMAIN", "SCOPE", "CREDENTIALS"]);
+                for entry in &stored {
+                    table.push_row(vec![
+                        entry.domain.clone(),
+                        entry.scope.clone(),
+                        entry.summary.clone(),
+                    ]);
+                }
+                table.render()
+            })?;
+        }
+        AuthCommand::Delete { domain, scope, yes } => {
+            let key = CredentialKey::new(domain.into(), scope);
+            if !manager.has_credentials(&key)? {
+                return Err(AuthError::MissingCredentials(key.to_string()).into());
+            }
+            if !confirm(&format!("Delete stored credentials for {key}?"), yes)? {
+                bail!("Deletion of {key} credentials cancelled");
+            }
+            manager.delete_credentials(&key)?;
+            println!("Deleted credentials for {key}");
+        }
+        AuthCommand::Rotate { domain, scope } => {
+            let key = CredentialKey::new(domain.into(), scope);
+            let secret = prompt_for_credentials(&key)?;
+            manager.set_credentials(&key, &secret)?;
+            println!(
+                "Rotated credentials for {} ({})",
+                key,
+                secret.redacted_summary()
+            );
+        }
+        AuthCommand::Test { domain, scope } => {
+            let key = CredentialKey::new(domain.into(), scope);
+            let api_config = config.api_config(key.domain())?;
+            let base_url = api_config.base_url.to_string();
+
+            block_on(async {
+                let auth = service_auth(&config, &manager, &key).await?;
+                probe(&manager, &key, api_config, auth)
+                    .await
+                    .with_context(|| format!("Authenticated request for {key} failed"))
+            })??;
+
+            let report = AuthTestReport {
+                domain: key.domain().to_string(),
+                scope: key.scope().to_string(),
+                base_url,
+                authenticated: true,
+            };
+            emit(format, &report, || {
+                output::fields(&[
+                    ("Credentials", key.to_string()),
+                    ("Endpoint", report.base_url.clone()),
+                    ("Result", "authenticated".into()),
+                ])
+            })?;
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Issues a cheap authenticated read against the domain's API.
+///
+/// Redfish is read through a session that is logged out afterwards, so probes do not use up
+/// the BMC's session slots.
+pub(crate) async fn probe(
+    credentials: &CredentialManager,
+    key: &CredentialKey,
+    config: ApiClientConfig,
+    auth: AuthContext,
+) -> Result<()> {
+    match key.domain() {
+        CredentialDomain::FabreX => {
+            FabrexClient::new(config)?
+                .with_auth(auth)
+                .list_fabrics()
+                .await?;
+        }
+        CredentialDomain::Gryf => {
+            GryfClient::new(config)?
+                .with_auth(auth)
+                .list_workloads()
+                .await?;
+        }
+        CredentialDomain::Supernode => {
+            SupernodeClient::new(config)?
+                .with_auth(auth)
+                .list_nodes()
+                .await?;
+        }
+        CredentialDomain::Redfish => {
+            let client = RedfishClient::new(config)?;
+            let sessions =
+                RedfishSessionManager::new(client.clone(), credentials.clone(), key.clone());
+            let result = sessions
+                .call(|auth| {
+                    let client = client.clone().with_auth(auth);
+                    async move { client.session_service().await }
+                })
+                .await;
+            let logout = sessions.logout().await;
+            result?;
+            logout.context("Logging out of the Redfish session")?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use super::output::{emit, or_dash, Table};
+use super::CONFIG_ERROR;
+use crate::cli::{ConfigCommand, OutputFormat};
+use crate::config::{ConfigProblem, KeyProvenance, LoadedConfig};
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::Value;
+use std::process::ExitCode;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ConfigEntry<'a> {
+    key: &'a str,
+    value: Value,
+    #[serde(flatten)]
+    provenance: &'a KeyProvenance,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ValidationReport<'a> {
+    valid: bool,
+    errors: Vec<ConfigProblem>,
+    unknown_keys: Vec<&'a str>,
+}
+
+pub fn run(loaded: LoadedConfig, command: ConfigCommand, format: OutputFormat) -> Result<ExitCode> {
+    match command {
+        ConfigCommand::Show => {
+            let values = loaded.config.flattened();
+            let entries: Vec<ConfigEntry> = loaded
+                .provenance
+                .iter()
+                .map(|(key, provenance)| ConfigEntry {
+                    key,
+                    value: redact(key, values.get(key).cloned().unwrap_or(Value::Null)),
+                    provenance,
+                })
+                .collect();
+
+            emit(format, &entries, || {
+                let mut table = Table::new(&["KEY", "VALUE", "SOURCE", "OVERRIDES"]);
+                for entry in &entries {
+                    table.push_row(vec![
+                        entry.key.to_string(),
+                        display_value(&entry.value),
+                        entry.provenance.source.to_string(),
+                        entry
+                            .provenance
+                            .overrides
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    ]);
+                }
+                table.render()
+            })?;
+            warn_unknown_keys(&loaded);
+        }
+        ConfigCommand::Validate => {
+            let errors = loaded.config.validate();
+            let report = ValidationReport {
+                valid: errors.is_empty(),
+                errors,
+                unknown_keys: loaded.unknown_keys(),
+            };
+
+            emit(format, &report, || {
+                if report.valid {
+                    return "Configuration is valid\n".to_string();
+                }
+                let mut table = Table::new(&["KEY", "PROBLEM"]);
+                for problem in &report.errors {
+                    table.push_row(vec![problem.key.clone(), problem.message.clone()]);
+                }
+                table.render()
+            })?;
+            warn_unknown_keys(&loaded);
+
+            if !report.valid {
+                return Ok(ExitCode::from(CONFIG_ERROR));
+            }
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Unknown keys are ignored by the loader, which usually means a typo or a misplaced table.
+fn warn_unknown_keys(loaded: &LoadedConfig) {
+    for key in loaded.unknown_keys() {
+        let source = &loaded.provenance[key].source;
+        eprintln!("warning: unrecognised configuration key '{key}' from {source}");
+    }
+}
+
+/// Secrets such as PKCS#12 passwords and authorization headers are shown as set or unset,
+/// never echoed. Keyring references hold no secret and are shown as they are.
+fn redact(key: &str, value: Value) -> Value {
+    if value
+        .as_str()
+        .is_some_and(|text| text.starts_with("keyring:"))
+    {
+        return value;
+    }
+    let secret_header = key.split_once(".headers.").is_some_and(|(_, name)| {
+        let name = name.to_ascii_lowercase();
+        ["auth", "token", "secret", "key", "cookie"]
+            .iter()
+            .any(|marker| name.contains(marker))
+    });
+    if (key.ends_with("password") || secret_header) && !value.is_null() {
+        Value::String("<redacted>".into())
+    } else {
+        value
+    }
+}
+
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::Null => or_dash(None),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
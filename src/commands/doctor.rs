@@ -0,0 +1,367 @@
+use super::auth::probe;
+use super::block_on;
+use super::output::{emit, Table};
+use crate::cli::OutputFormat;
+use crate::config::AppConfig;
+use crate::services::api::{AuthContext, ClientCredentialsGrant, HttpClient, ProxyMode};
+use crate::services::auth::{CredentialDomain, CredentialKey, CredentialManager};
+use anyhow::Result;
+use serde::Serialize;
+use std::fmt;
+use std::process::ExitCode;
+use tokio::net::lookup_host;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckStatus::Pass => write!(f, "PASS"),
+            CheckStatus::Warn => write!(f, "WARN"),
+            CheckStatus::Fail => write!(f, "FAIL"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Check {
+    name: String,
+    status: CheckStatus,
+    detail: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct DoctorReport {
+    checks: Vec<Check>,
+    passed: usize,
+    warnings: usize,
+    failed: usize,
+}
+
+impl DoctorReport {
+    fn record(&mut self, name: impl Into<String>, status: CheckStatus, detail: impl Into<String>) {
+        match status {
+            CheckStatus::Pass => self.passed += 1,
+            CheckStatus::Warn => self.warnings += 1,
+            CheckStatus::Fail => self.failed += 1,
+        }
+        self.checks.push(Check {
+            name: name.into(),
+            status,
+            detail: detail.into(),
+        });
+    }
+}
+
+pub fn run(config: AppConfig, format: OutputFormat) -> Result<ExitCode> {
+    let manager = CredentialManager::with_default_keyring().with_interactive(false);
+    let mut report = DoctorReport::default();
+
+    match manager.list_credentials() {
+        Ok(entries) => report.record(
+            "Keyring backend",
+            CheckStatus::Pass,
+            format!("reachable; {} stored credential(s)", entries.len()),
+        ),
+        Err(err) => report.record("Keyring backend", CheckStatus::Fail, err.to_string()),
+    }
+
+    block_on(async {
+        for domain in CredentialDomain::ALL {
+            check_domain(&config, &manager, domain, &mut report).await;
+        }
+    })?;
+
+    emit(format, &report, || {
+        let mut table = Table::new(&["STATUS", "CHECK", "DETAIL"]);
+        for check in &report.checks {
+            table.push_row(vec![
+                check.status.to_string(),
+                check.name.clone(),
+                check.detail.clone(),
+            ]);
+        }
+        let mut rendered = table.render();
+        rendered.push_str(&format!(
+            "\n{} passed, {} warning(s), {} failed\n",
+            report.passed, report.warnings, report.failed
+        ));
+        rendered
+    })?;
+
+    if report.failed > 0 {
+        return Ok(ExitCode::FAILURE);
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Runs the credential, DNS, TLS and authenticated-request checks for one service.
+///
+/// Later checks are skipped once an earlier one fails, so each problem is reported once.
+async fn check_domain(
+    config: &AppConfig,
+    manager: &CredentialManager,
+    domain: CredentialDomain,
+    report: &mut DoctorReport,
+) {
+    let key = CredentialKey::default(domain.clone());
+
+    let Some(base_url) = config.base_url(&domain) else {
+        report.record(
+            format!("{domain} endpoint"),
+            CheckStatus::Warn,
+            "no base URL configured; skipping",
+        );
+        return;
+    };
+
+    let auth = match manager.auth_context(&key) {
+        Ok(Some(auth)) => {
+            report.record(
+                format!("{domain} credentials"),
+                CheckStatus::Pass,
+                format!("stored for {key}"),
+            );
+            Some(auth)
+        }
+        Ok(None) => {
+            report.record(
+                format!("{domain} credentials"),
+                CheckStatus::Fail,
+                format!("none stored for {key}; run `fabrexlens auth-init`"),
+            );
+            None
+        }
+        Err(err) => {
+            report.record(
+                format!("{domain} credentials"),
+                CheckStatus::Fail,
+                err.to_string(),
+            );
+            None
+        }
+    };
+
+    let api_config = match config.api_config(&domain) {
+        Ok(api_config) => api_config,
+        Err(err) => {
+            report.record(
+                format!("{domain} client settings"),
+                CheckStatus::Fail,
+                format!("{base_url}: {err}"),
+            );
+            return;
+        }
+    };
+
+    let url = &api_config.base_url;
+    // Behind a proxy only the proxy itself has to resolve locally.
+    let (dns_check, resolved) = match &api_config.proxy {
+        ProxyMode::Proxy(options) => (format!("{domain} proxy DNS"), &options.url),
+        _ => (format!("{domain} DNS"), url),
+    };
+    let Some(dns_host) = resolved.host_str() else {
+        report.record(
+            dns_check,
+            CheckStatus::Fail,
+            format!("{resolved} has no host"),
+        );
+        return;
+    };
+    let port = resolved.port_or_known_default().unwrap_or_default();
+    match lookup_host((dns_host, port))
+        .await
+        .map(|mut addresses| addresses.next())
+    {
+        Ok(Some(address)) => report.record(
+            dns_check,
+            CheckStatus::Pass,
+            format!("{dns_host} resolved to {}", address.ip()),
+        ),
+        Ok(None) => {
+            report.record(
+                dns_check,
+                CheckStatus::Fail,
+                format!("{dns_host} resolved to no addresses"),
+            );
+            return;
+        }
+        Err(err) => {
+            report.record(dns_check, CheckStatus::Fail, format!("{dns_host}: {err}"));
+            return;
+        }
+    }
+
+    let host = url.host_str().unwrap_or(base_url);
+    let is_https = url.scheme() == "https";
+    let insecure = api_config.tls.insecure_skip_verify;
+    let ping = match HttpClient::new(api_config.clone()) {
+        Ok(client) => client.ping().await,
+        Err(err) => Err(err),
+    };
+    match ping {
+        Ok(status) if is_https && insecure => report.record(
+            format!("{domain} TLS"),
+            CheckStatus::Warn,
+            format!(
+                "{host} answered (HTTP {}) but certificate verification is disabled",
+                status.as_u16()
+            ),
+        ),
+        Ok(status) if is_https => report.record(
+            format!("{domain} TLS"),
+            CheckStatus::Pass,
+            format!("handshake with {host} completed (HTTP {})", status.as_u16()),
+        ),
+        Ok(status) => report.record(
+            format!("{domain} TLS"),
+            CheckStatus::Warn,
+            format!(
+                "{host} answered over plain HTTP (HTTP {}); credentials are sent unencrypted",
+                status.as_u16()
+            ),
+        ),
+        Err(err) => {
+            report.record(format!("{domain} TLS"), CheckStatus::Fail, err.to_string());
+            return;
+        }
+    }
+
+    // OAuth2 services are called with an access token, never with the stored client secret.
+    let auth = match (auth, config.client_credentials(&domain)) {
+        (Some(_), Ok(Some(grant))) => {
+            match check_token_endpoint(config, manager, &key, grant, report).await {
+                Some(token) => Some(token),
+                None => return,
+            }
+        }
+        (auth, Ok(_)) => auth,
+        (_, Err(err)) => {
+            report.record(
+                format!("{domain} OAuth2 token endpoint"),
+                CheckStatus::Fail,
+                err.to_string(),
+            );
+            return;
+        }
+    };
+
+    let check_name = match domain {
+        CredentialDomain::Redfish => format!("{domain} session service"),
+        _ => format!("{domain} authenticated GET"),
+    };
+    match auth {
+        Some(auth) => match probe(manager, &key, api_config, auth).await {
+            Ok(()) => report.record(check_name, CheckStatus::Pass, "2xx response"),
+            Err(err) => report.record(check_name, CheckStatus::Fail, format!("{err:#}")),
+        },
+        None => report.record(check_name, CheckStatus::Warn, "skipped; no credentials"),
+    }
+}
+
+/// Exchanges the stored client credentials for an access token at the service's OAuth2 token
+/// endpoint, returning `None` once a failure has been reported.
+async fn check_token_endpoint(
+    config: &AppConfig,
+    manager: &CredentialManager,
+    key: &CredentialKey,
+    grant: ClientCredentialsGrant,
+    report: &mut DoctorReport,
+) -> Option<AuthContext> {
+    let check_name = format!("{} OAuth2 token endpoint", key.domain());
+    let token_url = grant.token_url.clone();
+    let exchanged = match config.token_endpoint_config(key.domain(), &grant) {
+        Ok(endpoint) => grant
+            .authorize(manager, key, endpoint)
+            .await
+            .map_err(|err| format!("{token_url}: {err}")),
+        Err(err) => Err(err.to_string()),
+    };
+    match exchanged {
+        Ok(auth) => {
+            report.record(
+                check_name,
+                CheckStatus::Pass,
+                format!("access token issued by {token_url}"),
+            );
+            Some(auth)
+        }
+        Err(detail) => {
+            report.record(check_name, CheckStatus::Fail, detail);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AuthMode;
+    use crate::services::auth::tests::MemoryStore;
+    use crate::services::auth::CredentialSecret;
+    use httpmock::prelude::*;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn oauth2_services_are_probed_with_an_exchanged_token() {
+        let server = MockServer::start();
+        let token = server.mock(|when, then| {
+            when.method(POST)
+                .path("/oauth2/token")
+                .header("authorization", "Basic bGVuczpzM2NyM3Q=");
+            then.status(200).json_body(json!({
+                "access_token": "eyJ0b2tlbiI",
+                "token_type": "Bearer",
+                "expires_in": 3600
+            }));
+        });
+        let fabrics = server.mock(|when, then| {
+            when.method(GET)
+                .path_contains("/fabrics")
+                .header("authorization", "Bearer eyJ0b2tlbiI");
+            then.status(200)
+                .json_body(json!({ "items": [], "next": null }));
+        });
+        let _root = server.mock(|when, then| {
+            when.method(GET).path("/api/");
+            then.status(200);
+        });
+
+        let mut config = AppConfig::default();
+        config.fabrex.base_url = Some(server.url("/api/"));
+        config.fabrex.auth.mode = AuthMode::Oauth2;
+        config.fabrex.auth.token_url = Some(server.url("/oauth2/token"));
+        let manager = CredentialManager::new(Arc::new(MemoryStore::new()));
+        let key = CredentialKey::default(CredentialDomain::FabreX);
+        manager
+            .set_credentials(
+                &key,
+                &CredentialSecret {
+                    username: "lens".into(),
+                    password: "s3cr3t".into(),
+                    api_token: None,
+                },
+            )
+            .unwrap();
+
+        let mut report = DoctorReport::default();
+        check_domain(&config, &manager, CredentialDomain::FabreX, &mut report).await;
+
+        assert_eq!(report.failed, 0, "{:?}", report.checks);
+        let exchange = report
+            .checks
+            .iter()
+            .find(|check| check.name == "FabreX OAuth2 token endpoint")
+            .unwrap();
+        assert_eq!(exchange.status, CheckStatus::Pass);
+        token.assert_hits(1);
+        fabrics.assert_hits(1);
+    }
+}
@@ -0,0 +1,303 @@
+use super::output::{self, emit, or_dash};
+use super::{block_on, request_accepted, service_context, REQUEST_NOT_ACCEPTED, WAIT_TIMED_OUT};
+use crate::cli::{EndpointsCommand, OutputFormat};
+use crate::config::AppConfig;
+use crate::services::api::{FabrexClient, FabrexEndpoint, FabrexReassignmentResult};
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Serialize;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+use tokio::time;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReassignmentReport {
+    fabric_id: String,
+    endpoint_id: String,
+    endpoint_name: String,
+    previous_supernode_id: Option<String>,
+    target_supernode_id: String,
+    dry_run: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request: Option<FabrexReassignmentResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attached_supernode_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    completed: Option<bool>,
+}
+
+pub fn run(config: AppConfig, command: EndpointsCommand, format: OutputFormat) -> Result<ExitCode> {
+    let services = service_context(config);
+
+    match command {
+        EndpointsCommand::Reassign {
+            fabric,
+            endpoint,
+            to,
+            dry_run,
+            wait,
+            timeout,
+            poll_interval,
+        } => {
+            let report = block_on(async {
+                let fabrex = services.fabrex_client().await?;
+                let supernodes = services.supernode_client().await?;
+
+                let fabrics = fabrex.list_fabrics().await.context("Listing fabrics")?;
+                if !fabrics.iter().any(|candidate| candidate.id == fabric) {
+                    bail!("Fabric {fabric} not found");
+                }
+
+                let current = find_endpoint(&fabrex, &fabric, &endpoint)
+                    .await?
+                    .ok_or_else(|| anyhow!("Endpoint {endpoint} not found on fabric {fabric}"))?;
+
+                let nodes = supernodes
+                    .list_nodes()
+                    .await
+                    .context("Listing supernodes")?;
+                if !nodes.iter().any(|node| node.id == to) {
+                    bail!("Supernode {to} not found");
+                }
+
+                let mut report = ReassignmentReport {
+                    fabric_id: fabric.clone(),
+                    endpoint_id: current.id.clone(),
+                    endpoint_name: current.name.clone(),
+                    previous_supernode_id: current.attached_supernode_id.clone(),
+                    target_supernode_id: to.clone(),
+                    dry_run,
+                    request: None,
+                    attached_supernode_id: None,
+                    completed: None,
+                };
+                if dry_run {
+                    return Ok(report);
+                }
+
+                let result = fabrex
+                    .reassign_endpoint(&fabric, &endpoint, &to)
+                    .await
+                    .with_context(|| format!("Reassigning endpoint {endpoint}"))?;
+                let accepted = request_accepted(&result.status);
+                report.request = Some(result);
+
+                if wait && accepted {
+                    let deadline = Instant::now() + Duration::from_secs(timeout);
+                    let interval = Duration::from_secs(poll_interval.max(1));
+                    let (attached, completed) =
+                        wait_for_attachment(&fabrex, &fabric, &endpoint, &to, deadline, interval)
+                            .await?;
+                    report.attached_supernode_id = attached;
+                    report.completed = Some(completed);
+                }
+                Ok(report)
+            })??;
+
+            emit(format, &report, || render_report(&report))?;
+            Ok(exit_code(&report))
+        }
+    }
+}
+
+/// Exit code for a reassignment: not accepted, timed out waiting, or success.
+fn exit_code(report: &ReassignmentReport) -> ExitCode {
+    if let Some(request) = &report.request {
+        if !request_accepted(&request.status) {
+            return ExitCode::from(REQUEST_NOT_ACCEPTED);
+        }
+    }
+    if report.completed == Some(false) {
+        return ExitCode::from(WAIT_TIMED_OUT);
+    }
+    ExitCode::SUCCESS
+}
+
+async fn find_endpoint(
+    client: &FabrexClient,
+    fabric_id: &str,
+    endpoint_id: &str,
+) -> Result<Option<FabrexEndpoint>> {
+    let endpoints = client
+        .list_endpoints(fabric_id)
+        .await
+        .with_context(|| format!("Fetching endpoints for fabric {fabric_id}"))?;
+    Ok(endpoints
+        .into_iter()
+        .find(|endpoint| endpoint.id == endpoint_id))
+}
+
+/// Polls the endpoint until it reports `target` as its supernode or `deadline` passes.
+///
+/// Returns the last observed attachment and whether the target was reached.
+async fn wait_for_attachment(
+    client: &FabrexClient,
+    fabric_id: &str,
+    endpoint_id: &str,
+    target: &str,
+    deadline: Instant,
+    interval: Duration,
+) -> Result<(Option<String>, bool)> {
+    loop {
+        let attached = find_endpoint(client, fabric_id, endpoint_id)
+            .await?
+            .and_then(|endpoint| endpoint.attached_supernode_id);
+        if attached.as_deref() == Some(target) {
+            return Ok((attached, true));
+        }
+
+        let now = Instant::now();
+        if now >= deadline {
+            eprintln!("Timed out waiting for endpoint {endpoint_id} to attach to {target}");
+            return Ok((attached, false));
+        }
+
+        eprintln!(
+            "Waiting for endpoint {endpoint_id} to attach to {target} (currently {})",
+            attached.as_deref().unwrap_or("unattached")
+        );
+        time::sleep(interval.min(deadline - now)).await;
+    }
+}
+
+fn render_report(report: &ReassignmentReport) -> String {
+    let mut entries = vec![
+        ("Fabric", report.fabric_id.clone()),
+        (
+            "Endpoint",
+            format!("{} ({})", report.endpoint_name, report.endpoint_id),
+        ),
+        (
+            "Current supernode",
+            or_dash(report.previous_supernode_id.as_deref()),
+        ),
+        ("Target supernode", report.target_supernode_id.clone()),
+    ];
+
+    if report.dry_run {
+        entries.push(("Dry run", "validated; no request sent".into()));
+    }
+    if let Some(request) = &report.request {
+        entries.push(("Request", request.request_id.clone()));
+        entries.push(("Status", request.status.clone()));
+        if let Some(message) = &request.message {
+            entries.push(("Message", message.clone()));
+        }
+    }
+    if let Some(completed) = report.completed {
+        entries.push((
+            "Attached supernode",
+            or_dash(report.attached_supernode_id.as_deref()),
+        ));
+        entries.push((
+            "Completed",
+            if completed { "yes" } else { "no (timed out)" }.into(),
+        ));
+    }
+
+    output::fields(&entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::api::ApiClientConfig;
+    use httpmock::prelude::*;
+    use serde_json::json;
+
+    fn endpoints_attached_to<'a>(server: &'a MockServer, supernode: &str) -> httpmock::Mock<'a> {
+        server.mock(|when, then| {
+            when.method(GET).path("/fabrics/fab-1/endpoints");
+            then.status(200).json_body(json!({
+                "items": [{
+                    "id": "ep-9",
+                    "name": "GPU shelf",
+                    "attachedSupernodeId": supernode,
+                    "status": "assigned"
+                }],
+                "next": null
+            }));
+        })
+    }
+
+    fn client(server: &MockServer) -> FabrexClient {
+        FabrexClient::new(ApiClientConfig::try_from_url(&server.url("/")).unwrap()).unwrap()
+    }
+
+    fn report(status: &str, completed: Option<bool>) -> ReassignmentReport {
+        ReassignmentReport {
+            fabric_id: "fab-1".into(),
+            endpoint_id: "ep-9".into(),
+            endpoint_name: "GPU shelf".into(),
+            previous_supernode_id: Some("sn-1".into()),
+            target_supernode_id: "sn-2".into(),
+            dry_run: false,
+            request: Some(FabrexReassignmentResult {
+                request_id: "req-1".into(),
+                status: status.into(),
+                message: None,
+            }),
+            attached_supernode_id: None,
+            completed,
+        }
+    }
+
+    #[tokio::test]
+    async fn wait_returns_once_the_endpoint_reaches_the_target() {
+        let server = MockServer::start();
+        let mock = endpoints_attached_to(&server, "sn-2");
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let (attached, completed) = wait_for_attachment(
+            &client(&server),
+            "fab-1",
+            "ep-9",
+            "sn-2",
+            deadline,
+            Duration::from_secs(1),
+        )
+        .await
+        .unwrap();
+        assert_eq!(attached.as_deref(), Some("sn-2"));
+        assert!(completed);
+        mock.assert_hits(1);
+    }
+
+    #[tokio::test]
+    async fn wait_gives_up_at_the_deadline() {
+        let server = MockServer::start();
+        let mock = endpoints_attached_to(&server, "sn-1");
+
+        let deadline = Instant::now() + Duration::from_millis(500);
+        let (attached, completed) = wait_for_attachment(
+            &client(&server),
+            "fab-1",
+            "ep-9",
+            "sn-2",
+            deadline,
+            Duration::from_millis(50),
+        )
+        .await
+        .unwrap();
+        assert_eq!(attached.as_deref(), Some("sn-1"));
+        assert!(!completed);
+        assert!(mock.hits() >= 2);
+        assert_eq!(
+            exit_code(&report("InProgress", Some(completed))),
+            ExitCode::from(WAIT_TIMED_OUT)
+        );
+    }
+
+    #[test]
+    fn exit_codes_reflect_acceptance_and_completion() {
+        assert_eq!(
+            exit_code(&report("accepted", Some(true))),
+            ExitCode::SUCCESS
+        );
+        assert_eq!(exit_code(&report("accepted", None)), ExitCode::SUCCESS);
+        assert_eq!(
+            exit_code(&report("rejected", None)),
+            ExitCode::from(REQUEST_NOT_ACCEPTED)
+        );
+    }
+}
@@ -0,0 +1,203 @@
+use super::{
+    AUTH_REJECTED, CONFIG_ERROR, HTTP_ERROR, INVALID_RESPONSE, MISSING_CREDENTIALS, NETWORK_ERROR,
+};
+use crate::cli::ErrorFormat;
+use crate::config::AppConfigError;
+use crate::services::api::http::ApiError;
+use crate::services::api::{failed_request_curl, ErrorDetails};
+use crate::services::auth::AuthError;
+use reqwest::StatusCode;
+use serde::Serialize;
+use std::process::ExitCode;
+
+/// Broad failure categories, each with its own documented exit code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    Config,
+    MissingCredentials,
+    AuthRejected,
+    Http,
+    Network,
+    InvalidResponse,
+    Other,
+}
+
+impl ErrorKind {
+    pub fn exit_code(self) -> u8 {
+        match self {
+            ErrorKind::Config => CONFIG_ERROR,
+            ErrorKind::MissingCredentials => MISSING_CREDENTIALS,
+            ErrorKind::AuthRejected => AUTH_REJECTED,
+            ErrorKind::Http => HTTP_ERROR,
+            ErrorKind::Network => NETWORK_ERROR,
+            ErrorKind::InvalidResponse => INVALID_RESPONSE,
+            ErrorKind::Other => 1,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ErrorReport {
+    kind: ErrorKind,
+    exit_code: u8,
+    message: String,
+    causes: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    http_status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<ErrorDetails>,
+    /// Redacted `curl` command reproducing the failed request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    curl: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ErrorEnvelope {
+    error: ErrorReport,
+}
+
+/// Finds the outermost typed error in the chain and maps it to an [`ErrorKind`].
+pub fn classify(err: &anyhow::Error) -> (ErrorKind, Option<StatusCode>) {
+    for cause in err.chain() {
+        if let Some(api) = cause.downcast_ref::<ApiError>() {
+            return match api.last_attempt() {
+                // `419` is treated as an expired session by the transport layer as well.
+                ApiError::HttpStatus { status, .. }
+                    if *status == StatusCode::FORBIDDEN || api.is_unauthorized() =>
+                {
+                    (ErrorKind::AuthRejected, Some(*status))
+                }
+                ApiError::HttpStatus { status, .. } => (ErrorKind::Http, Some(*status)),
+                ApiError::Request(source) => (request_kind(source), None),
+                ApiError::Credentials(auth) => (credential_kind(auth), None),
+                ApiError::CircuitOpen { .. } | ApiError::NotRecorded(_) => {
+                    (ErrorKind::Network, None)
+                }
+                ApiError::Deserialize { .. }
+                | ApiError::MissingAuthToken
+                | ApiError::TooManyItems { .. } => (ErrorKind::InvalidResponse, None),
+                ApiError::Url(_)
+                | ApiError::Tls(_)
+                | ApiError::Proxy(_)
+                | ApiError::Limits(_)
+                | ApiError::Header(_)
+                | ApiError::OAuth(_)
+                | ApiError::Cassette(_) => (ErrorKind::Config, None),
+                // `last_attempt` never returns these wrappers. Listing them instead of using a
+                // wildcard keeps the match exhaustive, so new variants must be classified here.
+                ApiError::Retried { .. } | ApiError::WithRequest { .. } => (ErrorKind::Other, None),
+            };
+        }
+        if let Some(auth) = cause.downcast_ref::<AuthError>() {
+            return (credential_kind(auth), None);
+        }
+        if cause.is::<AppConfigError>() || cause.is::<config::ConfigError>() {
+            return (ErrorKind::Config, None);
+        }
+        if let Some(source) = cause.downcast_ref::<reqwest::Error>() {
+            return (request_kind(source), None);
+        }
+    }
+    (ErrorKind::Other, None)
+}
+
+fn credential_kind(auth: &AuthError) -> ErrorKind {
+    match auth {
+        AuthError::InteractiveDisabled(_) | AuthError::MissingCredentials(_) => {
+            ErrorKind::MissingCredentials
+        }
+        _ => ErrorKind::Other,
+    }
+}
+
+fn request_kind(source: &reqwest::Error) -> ErrorKind {
+    if source.is_decode() {
+        ErrorKind::InvalidResponse
+    } else {
+        ErrorKind::Network
+    }
+}
+
+/// Structured fields from the first API error in the chain whose body was decoded.
+fn error_details(err: &anyhow::Error) -> Option<&ErrorDetails> {
+    err.chain()
+        .filter_map(|cause| cause.downcast_ref::<ApiError>())
+        .find_map(ApiError::details)
+}
+
+/// Writes `err` to stderr in the requested format and returns the matching exit code.
+pub fn report(err: &anyhow::Error, format: ErrorFormat) -> ExitCode {
+    let (kind, status) = classify(err);
+    let exit_code = kind.exit_code();
+
+    match format {
+        ErrorFormat::Text => eprintln!("Error: {err:?}"),
+        ErrorFormat::Json => {
+            let envelope = ErrorEnvelope {
+                error: ErrorReport {
+                    kind,
+                    exit_code,
+                    message: err.to_string(),
+                    causes: err.chain().skip(1).map(ToString::to_string).collect(),
+                    http_status: status.map(|status| status.as_u16()),
+                    details: error_details(err).cloned(),
+                    curl: failed_request_curl(err),
+                },
+            };
+            match serde_json::to_string(&envelope) {
+                Ok(line) => eprintln!("{line}"),
+                Err(_) => eprintln!("Error: {err:?}"),
+            }
+        }
+    }
+
+    ExitCode::from(exit_code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    fn http_error(status: StatusCode) -> anyhow::Error {
+        let result: Result<(), ApiError> = Err(ApiError::HttpStatus {
+            status,
+            body: String::new(),
+            details: None,
+        });
+        result.context("Listing fabrics").unwrap_err()
+    }
+
+    #[test]
+    fn classifies_http_statuses_through_context() {
+        assert_eq!(
+            classify(&http_error(StatusCode::FORBIDDEN)),
+            (ErrorKind::AuthRejected, Some(StatusCode::FORBIDDEN))
+        );
+        let expired = StatusCode::from_u16(419).unwrap();
+        assert_eq!(
+            classify(&http_error(expired)),
+            (ErrorKind::AuthRejected, Some(expired))
+        );
+        assert_eq!(
+            classify(&http_error(StatusCode::BAD_GATEWAY)),
+            (ErrorKind::Http, Some(StatusCode::BAD_GATEWAY))
+        );
+    }
+
+    #[test]
+    fn classifies_credential_and_untyped_errors() {
+        let missing = anyhow::Error::from(AuthError::MissingCredentials("Gryf [default]".into()));
+        assert_eq!(classify(&missing).0, ErrorKind::MissingCredentials);
+        assert_eq!(
+            ErrorKind::MissingCredentials.exit_code(),
+            MISSING_CREDENTIALS
+        );
+
+        let untyped = anyhow::anyhow!("something else");
+        assert_eq!(classify(&untyped), (ErrorKind::Other, None));
+        assert_eq!(ErrorKind::Other.exit_code(), 1);
+    }
+}
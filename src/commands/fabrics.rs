@@ -0,0 +1,103 @@
+use super::output::{self, emit, or_dash, Table};
+use super::{block_on, service_context};
+use crate::cli::{FabricsCommand, OutputFormat};
+use crate::config::AppConfig;
+use anyhow::{anyhow, Context, Result};
+use std::process::ExitCode;
+
+pub fn run(config: AppConfig, command: FabricsCommand, format: OutputFormat) -> Result<ExitCode> {
+    let services = service_context(config);
+
+    match command {
+        FabricsCommand::List => {
+            let fabrics = block_on(async {
+                let client = services.fabrex_client().await?;
+                client.list_fabrics().await.context("Listing fabrics")
+            })??;
+            emit(format, &fabrics, || {
+                let mut table = Table::new(&["ID", "NAME", "STATUS", "DESCRIPTION"]);
+                for fabric in &fabrics {
+                    table.push_row(vec![
+                        fabric.id.clone(),
+                        fabric.name.clone(),
+                        fabric.status.clone(),
+                        or_dash(fabric.description.as_deref()),
+                    ]);
+                }
+                table.render()
+            })?;
+        }
+        FabricsCommand::Show { fabric_id } => {
+            let fabrics = block_on(async {
+                let client = services.fabrex_client().await?;
+                client.list_fabrics().await.context("Listing fabrics")
+            })??;
+            let fabric = fabrics
+                .into_iter()
+                .find(|fabric| fabric.id == fabric_id)
+                .ok_or_else(|| anyhow!("Fabric {fabric_id} not found"))?;
+            emit(format, &fabric, || {
+                output::fields(&[
+                    ("ID", fabric.id.clone()),
+                    ("Name", fabric.name.clone()),
+                    ("Status", fabric.status.clone()),
+                    ("Description", or_dash(fabric.description.as_deref())),
+                ])
+            })?;
+        }
+        FabricsCommand::Usage { fabric_id } => {
+            let usage = block_on(async {
+                let client = services.fabrex_client().await?;
+                client
+                    .fabric_usage(&fabric_id)
+                    .await
+                    .with_context(|| format!("Fetching usage for fabric {fabric_id}"))
+            })??;
+            emit(format, &usage, || {
+                let mut rendered = output::fields(&[
+                    ("Fabric", usage.fabric_id.clone()),
+                    ("Utilization", format!("{:.1}%", usage.utilization_percent)),
+                    (
+                        "Endpoints",
+                        format!(
+                            "{}/{} assigned",
+                            usage.assigned_endpoints, usage.total_endpoints
+                        ),
+                    ),
+                ]);
+                if !usage.alerts.is_empty() {
+                    let mut table = Table::new(&["SEVERITY", "MESSAGE"]);
+                    for alert in &usage.alerts {
+                        table.push_row(vec![alert.severity.to_uppercase(), alert.message.clone()]);
+                    }
+                    rendered.push('\n');
+                    rendered.push_str(&table.render());
+                }
+                rendered
+            })?;
+        }
+        FabricsCommand::Endpoints { fabric_id } => {
+            let endpoints = block_on(async {
+                let client = services.fabrex_client().await?;
+                client
+                    .list_endpoints(&fabric_id)
+                    .await
+                    .with_context(|| format!("Fetching endpoints for fabric {fabric_id}"))
+            })??;
+            emit(format, &endpoints, || {
+                let mut table = Table::new(&["ID", "NAME", "STATUS", "SUPERNODE"]);
+                for endpoint in &endpoints {
+                    table.push_row(vec![
+                        endpoint.id.clone(),
+                        endpoint.name.clone(),
+                        endpoint.status.clone(),
+                        or_dash(endpoint.attached_supernode_id.as_deref()),
+                    ]);
+                }
+                table.render()
+            })?;
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,40 @@
+use crate::cli::{Cli, CompletionShell};
+use anyhow::{anyhow, Context, Result};
+use clap::CommandFactory;
+use clap_complete::env::Shells;
+use clap_mangen::Man;
+use std::io;
+use std::process::ExitCode;
+
+/// Name of the installed executable, used in completion scripts and the man page.
+const BIN_NAME: &str = "fabrexlens";
+
+/// Environment variable the completion scripts set when asking the binary for candidates.
+const COMPLETE_VAR: &str = "COMPLETE";
+
+/// Prints a registration script that calls back into the binary for candidates, so
+/// profile names and other dynamic values are always current.
+pub fn completions(shell: CompletionShell) -> Result<ExitCode> {
+    let shells = Shells::builtins();
+    let completer = shells
+        .completer(shell.name())
+        .ok_or_else(|| anyhow!("Completions are not supported for {}", shell.name()))?;
+    completer
+        .write_registration(
+            COMPLETE_VAR,
+            BIN_NAME,
+            BIN_NAME,
+            BIN_NAME,
+            &mut io::stdout(),
+        )
+        .context("Writing completion script")?;
+    Ok(ExitCode::SUCCESS)
+}
+
+pub fn manpage() -> Result<ExitCode> {
+    let command = Cli::command().name(BIN_NAME);
+    Man::new(command)
+        .render(&mut io::stdout())
+        .context("Writing man page")?;
+    Ok(ExitCode::SUCCESS)
+}
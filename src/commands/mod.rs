@@ -0,0 +1,128 @@
+pub mod auth;
+pub mod config;
+pub mod doctor;
+pub mod endpoints;
+pub mod errors;
+pub mod fabrics;
+pub mod generate;
+pub mod nodes;
+pub mod output;
+pub mod workloads;
+
+use crate::app::ServiceContext;
+use crate::config::AppConfig;
+use crate::services::auth::CredentialManager;
+use anyhow::{bail, Context, Result};
+use dialoguer::{theme::ColorfulTheme, Confirm};
+use std::future::Future;
+use std::io::{self, IsTerminal};
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+/// Exit code when a mutating request was answered but not accepted by the service.
+pub const REQUEST_NOT_ACCEPTED: u8 = 3;
+
+/// Exit code when `--wait` gave up before the requested change was observed.
+pub const WAIT_TIMED_OUT: u8 = 4;
+
+/// Exit code when the configuration could not be loaded or failed validation.
+pub const CONFIG_ERROR: u8 = 5;
+
+/// Exit code when a service needs credentials that are not stored.
+pub const MISSING_CREDENTIALS: u8 = 6;
+
+/// Exit code when a service rejected the stored credentials (HTTP 401, 403 or 419).
+pub const AUTH_REJECTED: u8 = 7;
+
+/// Exit code for any other non-success HTTP status.
+pub const HTTP_ERROR: u8 = 8;
+
+/// Exit code when a service could not be reached (DNS, connect, TLS, or timeout).
+pub const NETWORK_ERROR: u8 = 9;
+
+/// Exit code when a service answered with a body FabreXLens could not understand.
+pub const INVALID_RESPONSE: u8 = 10;
+
+/// Service context for one-shot CLI commands; never prompts for missing credentials.
+pub(crate) fn service_context(config: AppConfig) -> ServiceContext {
+    let credentials = Arc::new(CredentialManager::with_default_keyring().with_interactive(false));
+    ServiceContext::new(Arc::new(config), credentials)
+}
+
+/// Runs `future` on a fresh runtime. Build API clients inside the future that uses them: a
+/// client's connection pool belongs to the runtime it was created on.
+pub(crate) fn block_on<F: Future>(future: F) -> Result<F::Output> {
+    let runtime = Runtime::new().context("Starting tokio runtime")?;
+    Ok(runtime.block_on(future))
+}
+
+/// Asks the operator to confirm a mutating operation unless `assume_yes` is set.
+pub(crate) fn confirm(prompt: &str, assume_yes: bool) -> Result<bool> {
+    confirm_with(prompt, assume_yes, io::stdin().is_terminal())
+}
+
+fn confirm_with(prompt: &str, assume_yes: bool, interactive: bool) -> Result<bool> {
+    if assume_yes {
+        return Ok(true);
+    }
+    if !interactive {
+        bail!("Refusing to continue without confirmation; pass --yes to proceed non-interactively");
+    }
+
+    Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .default(false)
+        .interact()
+        .context("Reading confirmation")
+}
+
+/// Whether a request status means the service took the request on.
+///
+/// Case, surrounding whitespace and `_`/`-` separators are ignored, so `InProgress`,
+/// `in-progress` and `IN_PROGRESS` all match.
+pub(crate) fn request_accepted(status: &str) -> bool {
+    let normalized: String = status
+        .trim()
+        .chars()
+        .filter(|c| !matches!(c, '_' | '-'))
+        .collect::<String>()
+        .to_lowercase();
+    matches!(
+        normalized.as_str(),
+        "accepted" | "pending" | "queued" | "inprogress" | "running" | "completed" | "succeeded"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_statuses_match_regardless_of_case_and_separators() {
+        for status in [
+            "accepted",
+            "Accepted",
+            "InProgress",
+            "in-progress",
+            "IN_PROGRESS",
+            "Completed ",
+            " queued",
+            "SUCCEEDED",
+        ] {
+            assert!(request_accepted(status), "{status:?} should be accepted");
+        }
+        for status in ["rejected", "failed", "in progress", "", "unknown"] {
+            assert!(
+                !request_accepted(status),
+                "{status:?} should not be accepted"
+            );
+        }
+    }
+
+    #[test]
+    fn confirmation_requires_yes_without_a_terminal() {
+        assert!(confirm_with("Proceed?", true, false).unwrap());
+        let err = confirm_with("Proceed?", false, false).unwrap_err();
+        assert!(err.to_string().contains("--yes"));
+    }
+}
@@ -0,0 +1,245 @@
+use super::output::{self, emit, Table};
+use super::{block_on, confirm, request_accepted, service_context, REQUEST_NOT_ACCEPTED};
+use crate::cli::{NodesCommand, OutputFormat};
+use crate::config::AppConfig;
+use crate::services::api::supernode::SupernodeHealth;
+use crate::services::api::SupernodeClient;
+use anyhow::{bail, Context, Result};
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::Path;
+use std::process::ExitCode;
+
+pub fn run(config: AppConfig, command: NodesCommand, format: OutputFormat) -> Result<ExitCode> {
+    let services = service_context(config);
+
+    match command {
+        NodesCommand::List => {
+            let nodes = block_on(async {
+                let client = services.supernode_client().await?;
+                client.list_nodes().await.context("Listing supernodes")
+            })??;
+            emit(format, &nodes, || {
+                let mut table = Table::new(&["ID", "NAME", "ROLE", "STATUS"]);
+                for node in &nodes {
+                    table.push_row(vec![
+                        node.id.clone(),
+                        node.name.clone(),
+                        node.role.clone(),
+                        node.status.clone(),
+                    ]);
+                }
+                table.render()
+            })?;
+        }
+        NodesCommand::Health { node_id, all } => {
+            let reports = block_on(async {
+                let client = services.supernode_client().await?;
+                collect_health(&client, node_id.as_deref(), all).await
+            })??;
+            if all {
+                emit(format, &reports, || render_health_table(&reports))?;
+            } else if let Some(report) = reports.first() {
+                emit(format, report, || render_health_detail(report))?;
+            }
+        }
+        NodesCommand::Action {
+            node_id,
+            action,
+            payload,
+            yes,
+        } => {
+            let body = payload.as_deref().map(read_payload).transpose()?;
+
+            if is_disruptive(&action) {
+                let prompt = format!("Run disruptive action '{action}' on node {node_id}?");
+                if !confirm(&prompt, yes)? {
+                    bail!("Action {action} on node {node_id} cancelled");
+                }
+            }
+
+            let response = block_on(async {
+                let client = services.supernode_client().await?;
+                client
+                    .invoke_action(&node_id, &action, body)
+                    .await
+                    .with_context(|| format!("Invoking {action} on node {node_id}"))
+            })??;
+            emit(format, &response, || {
+                output::fields(&[
+                    ("Request", response.request_id.clone()),
+                    ("Status", response.status.clone()),
+                ])
+            })?;
+
+            if !request_accepted(&response.status) {
+                return Ok(ExitCode::from(REQUEST_NOT_ACCEPTED));
+            }
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+async fn collect_health(
+    client: &SupernodeClient,
+    node_id: Option<&str>,
+    all: bool,
+) -> Result<Vec<SupernodeHealth>> {
+    let node_ids = match node_id {
+        Some(id) if !all => vec![id.to_string()],
+        _ => client
+            .list_nodes()
+            .await
+            .context("Listing supernodes")?
+            .into_iter()
+            .map(|node| node.id)
+            .collect(),
+    };
+
+    let mut reports = Vec::with_capacity(node_ids.len());
+    for id in node_ids {
+        let health = client
+            .node_health(&id)
+            .await
+            .with_context(|| format!("Fetching health for node {id}"))?;
+        reports.push(health);
+    }
+    Ok(reports)
+}
+
+fn render_health_table(reports: &[SupernodeHealth]) -> String {
+    let mut table = Table::new(&["NODE", "CPU", "MEMORY", "ISSUES"]);
+    for report in reports {
+        table.push_row(vec![
+            report.node_id.clone(),
+            format!("{:.1}%", report.cpu_percent),
+            format!("{:.1}%", report.memory_percent),
+            report
+                .issues
+                .iter()
+                .map(|issue| format!("{}: {}", issue.severity.to_uppercase(), issue.description))
+                .collect::<Vec<_>>()
+                .join("; "),
+        ]);
+    }
+    table.render()
+}
+
+fn render_health_detail(report: &SupernodeHealth) -> String {
+    let mut rendered = output::fields(&[
+        ("Node", report.node_id.clone()),
+        ("CPU", format!("{:.1}%", report.cpu_percent)),
+        ("Memory", format!("{:.1}%", report.memory_percent)),
+    ]);
+
+    rendered.push_str("\nIssues\n");
+    if report.issues.is_empty() {
+        rendered.push_str("  none reported\n");
+    } else {
+        let mut table = Table::new(&["SEVERITY", "DESCRIPTION"]);
+        for issue in &report.issues {
+            table.push_row(vec![
+                issue.severity.to_uppercase(),
+                issue.description.clone(),
+            ]);
+        }
+        rendered.push_str(&table.render());
+    }
+    rendered
+}
+
+/// Reads an action payload from a JSON file, or from stdin when the path is `-`.
+fn read_payload(path: &Path) -> Result<serde_json::Value> {
+    if path == Path::new("-") {
+        return serde_json::from_reader(io::stdin().lock()).context("Parsing payload from stdin");
+    }
+
+    let file =
+        File::open(path).with_context(|| format!("Opening payload file {}", path.display()))?;
+    serde_json::from_reader(BufReader::new(file))
+        .with_context(|| format!("Parsing payload file {}", path.display()))
+}
+
+/// Actions that interrupt a node and therefore need explicit confirmation.
+fn is_disruptive(action: &str) -> bool {
+    matches!(
+        action.to_lowercase().replace('_', "-").as_str(),
+        "restart"
+            | "force-restart"
+            | "reboot"
+            | "reset"
+            | "shutdown"
+            | "power-off"
+            | "poweroff"
+            | "power-cycle"
+            | "drain"
+            | "decommission"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::api::ApiClientConfig;
+    use httpmock::prelude::*;
+    use serde_json::json;
+
+    #[test]
+    fn disruptive_actions_ignore_case_and_separators() {
+        for action in [
+            "restart",
+            "Force_Restart",
+            "POWER-OFF",
+            "power_cycle",
+            "drain",
+        ] {
+            assert!(is_disruptive(action), "{action} should need confirmation");
+        }
+        for action in ["collect-logs", "identify", "refresh_inventory"] {
+            assert!(
+                !is_disruptive(action),
+                "{action} should not need confirmation"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn collects_health_for_one_or_every_node() {
+        let server = MockServer::start();
+        let nodes = server.mock(|when, then| {
+            when.method(GET).path("/nodes");
+            then.status(200).json_body(json!({
+                "items": [
+                    { "id": "node-1", "name": "A", "role": "compute", "status": "online" },
+                    { "id": "node-2", "name": "B", "role": "compute", "status": "online" }
+                ],
+                "next": null
+            }));
+        });
+        for id in ["node-1", "node-2"] {
+            server.mock(|when, then| {
+                when.method(GET).path(format!("/nodes/{id}/health"));
+                then.status(200).json_body(json!({
+                    "nodeId": id,
+                    "cpuPercent": 12.5,
+                    "memoryPercent": 40.0
+                }));
+            });
+        }
+        let client =
+            SupernodeClient::new(ApiClientConfig::try_from_url(&server.url("/")).unwrap()).unwrap();
+
+        let single = collect_health(&client, Some("node-2"), false)
+            .await
+            .unwrap();
+        assert_eq!(single.len(), 1);
+        assert_eq!(single[0].node_id, "node-2");
+        nodes.assert_hits(0);
+
+        let all = collect_health(&client, None, true).await.unwrap();
+        let ids: Vec<&str> = all.iter().map(|report| report.node_id.as_str()).collect();
+        assert_eq!(ids, vec!["node-1", "node-2"]);
+        nodes.assert_hits(1);
+    }
+}
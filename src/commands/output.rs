@@ -0,0 +1,109 @@
+use crate::cli::OutputFormat;
+use anyhow::Result;
+use serde::Serialize;
+
+/// Writes `value` to stdout in the requested format, building the table view lazily.
+pub fn emit<T>(format: OutputFormat, value: &T, table: impl FnOnce() -> String) -> Result<()>
+where
+    T: Serialize + ?Sized,
+{
+    match format {
+        OutputFormat::Table => print!("{}", table()),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(value)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml::to_string(value)?),
+    }
+    Ok(())
+}
+
+/// Column-aligned plain text table for terminal output.
+#[derive(Debug, Clone)]
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(headers: &[&str]) -> Self {
+        Self {
+            headers: headers.iter().map(|header| header.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn push_row(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+
+    pub fn render(&self) -> String {
+        let mut widths: Vec<usize> = self.headers.iter().map(|h| h.chars().count()).collect();
+        for row in &self.rows {
+            for (index, cell) in row.iter().enumerate() {
+                if let Some(width) = widths.get_mut(index) {
+                    *width = (*width).max(cell.chars().count());
+                }
+            }
+        }
+
+        let mut output = String::new();
+        write_line(&mut output, &self.headers, &widths);
+        for row in &self.rows {
+            write_line(&mut output, row, &widths);
+        }
+        output
+    }
+}
+
+/// Renders a two-column `label  value` listing for a single record.
+pub fn fields(entries: &[(&str, String)]) -> String {
+    let width = entries
+        .iter()
+        .map(|(label, _)| label.chars().count())
+        .max()
+        .unwrap_or_default();
+
+    entries
+        .iter()
+        .map(|(label, value)| format!("{label:<width$}  {value}\n"))
+        .collect()
+}
+
+pub fn or_dash(value: Option<&str>) -> String {
+    value.unwrap_or("—").to_string()
+}
+
+fn write_line(output: &mut String, cells: &[String], widths: &[usize]) {
+    let line = widths
+        .iter()
+        .enumerate()
+        .map(|(index, width)| {
+            let cell = cells.get(index).map(String::as_str).unwrap_or("");
+            format!("{cell:<width$}")
+        })
+        .collect::<Vec<_>>()
+        .join("  ");
+    output.push_str(line.trim_end());
+    output.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aligns_columns_to_widest_cell() {
+        let mut table = Table::new(&["ID", "NAME"]);
+        table.push_row(vec!["fab-1".into(), "Production".into()]);
+        table.push_row(vec!["f2".into(), "Lab".into()]);
+
+        assert_eq!(
+            table.render(),
+            "ID     NAME\nfab-1  Production\nf2     Lab\n"
+        );
+    }
+
+    #[test]
+    fn renders_label_value_fields() {
+        let rendered = fields(&[("ID", "fab-1".into()), ("Status", "Healthy".into())]);
+        assert_eq!(rendered, "ID      fab-1\nStatus  Healthy\n");
+    }
+}
@@ -0,0 +1,189 @@
+use super::output::{self, emit, or_dash, Table};
+use super::{block_on, confirm, request_accepted, service_context, REQUEST_NOT_ACCEPTED};
+use crate::cli::{OutputFormat, WorkloadsCommand};
+use crate::config::AppConfig;
+use crate::services::api::gryf::GryfReassignmentResult;
+use crate::services::api::{GryfClient, GryfWorkload};
+use anyhow::{bail, Context, Result};
+use std::process::ExitCode;
+
+pub fn run(config: AppConfig, command: WorkloadsCommand, format: OutputFormat) -> Result<ExitCode> {
+    let services = service_context(config);
+
+    match command {
+        WorkloadsCommand::List { state, owner } => {
+            let workloads: Vec<GryfWorkload> = block_on(async {
+                let client = services.gryf_client().await?;
+                client.list_workloads().await.context("Listing workloads")
+            })??
+            .into_iter()
+            .filter(|workload| matches_filter(Some(&workload.state), state.as_deref()))
+            .filter(|workload| matches_filter(workload.owner.as_ref(), owner.as_deref()))
+            .collect();
+            emit(format, &workloads, || {
+                let mut table = Table::new(&["ID", "NAME", "STATE", "OWNER"]);
+                for workload in &workloads {
+                    table.push_row(vec![
+                        workload.id.clone(),
+                        workload.name.clone(),
+                        workload.state.clone(),
+                        or_dash(workload.owner.as_deref()),
+                    ]);
+                }
+                table.render()
+            })?;
+        }
+        WorkloadsCommand::Show { workload_id } => {
+            let detail = block_on(async {
+                let client = services.gryf_client().await?;
+                client
+                    .workload(&workload_id)
+                    .await
+                    .with_context(|| format!("Fetching workload {workload_id}"))
+            })??;
+            emit(format, &detail, || {
+                let mut rendered = output::fields(&[
+                    ("ID", detail.workload.id.clone()),
+                    ("Name", detail.workload.name.clone()),
+                    ("State", detail.workload.state.clone()),
+                    ("Owner", or_dash(detail.workload.owner.as_deref())),
+                ]);
+
+                rendered.push_str("\nTasks\n");
+                if detail.tasks.is_empty() {
+                    rendered.push_str("  none reported\n");
+                } else {
+                    let mut table = Table::new(&["ID", "NODE", "STATUS"]);
+                    for task in &detail.tasks {
+                        table.push_row(vec![
+                            task.id.clone(),
+                            task.node.clone(),
+                            task.status.clone(),
+                        ]);
+                    }
+                    rendered.push_str(&table.render());
+                }
+
+                rendered.push_str("\nMetrics\n");
+                if detail.metrics.is_empty() {
+                    rendered.push_str("  none reported\n");
+                } else {
+                    let mut table = Table::new(&["KEY", "VALUE", "UNIT"]);
+                    for metric in &detail.metrics {
+                        table.push_row(vec![
+                            metric.key.clone(),
+                            metric.value.to_string(),
+                            or_dash(metric.unit.as_deref()),
+                        ]);
+                    }
+                    rendered.push_str(&table.render());
+                }
+                rendered
+            })?;
+        }
+        WorkloadsCommand::Reassign {
+            workload_id,
+            fabric,
+            reason,
+            yes,
+        } => {
+            let result = block_on(async {
+                let client = services.gryf_client().await?;
+                reassign(&client, &workload_id, &fabric, &reason, |prompt| {
+                    confirm(prompt, yes)
+                })
+                .await
+            })??;
+            emit(format, &result, || {
+                output::fields(&[
+                    ("Request", result.request_id.clone()),
+                    ("Status", result.status.clone()),
+                    ("Details", or_dash(result.details.as_deref())),
+                ])
+            })?;
+
+            if !request_accepted(&result.status) {
+                return Ok(ExitCode::from(REQUEST_NOT_ACCEPTED));
+            }
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Sends the reassignment once `confirm` approves it.
+async fn reassign(
+    client: &GryfClient,
+    workload_id: &str,
+    fabric: &str,
+    reason: &str,
+    confirm: impl FnOnce(&str) -> Result<bool>,
+) -> Result<GryfReassignmentResult> {
+    let prompt = format!("Reassign workload {workload_id} to fabric {fabric}?");
+    if !confirm(&prompt)? {
+        bail!("Reassignment of workload {workload_id} cancelled");
+    }
+    client
+        .reassign_workload(workload_id, fabric, Some(reason))
+        .await
+        .with_context(|| format!("Reassigning workload {workload_id}"))
+}
+
+fn matches_filter(value: Option<&String>, filter: Option<&str>) -> bool {
+    match filter {
+        Some(expected) => value.is_some_and(|actual| actual.eq_ignore_ascii_case(expected)),
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::api::ApiClientConfig;
+    use httpmock::prelude::*;
+
+    #[tokio::test]
+    async fn reassignment_is_only_sent_after_confirmation() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/workloads/wl-1/reassign")
+                .json_body(serde_json::json!({
+                    "targetFabricId": "fab-2",
+                    "reason": "rebalancing"
+                }));
+            then.status(202).json_body(serde_json::json!({
+                "requestId": "req-9",
+                "status": "InProgress"
+            }));
+        });
+        let client =
+            GryfClient::new(ApiClientConfig::try_from_url(&server.url("/")).unwrap()).unwrap();
+
+        let err = reassign(&client, "wl-1", "fab-2", "rebalancing", |_| Ok(false))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("cancelled"));
+        mock.assert_hits(0);
+
+        let mut prompted = String::new();
+        let result = reassign(&client, "wl-1", "fab-2", "rebalancing", |prompt| {
+            prompted = prompt.to_string();
+            Ok(true)
+        })
+        .await
+        .unwrap();
+        assert_eq!(prompted, "Reassign workload wl-1 to fabric fab-2?");
+        assert!(request_accepted(&result.status));
+        mock.assert_hits(1);
+    }
+
+    #[test]
+    fn filters_match_case_insensitively() {
+        let owner = "Ops".to_string();
+        assert!(matches_filter(Some(&owner), Some("ops")));
+        assert!(!matches_filter(Some(&owner), Some("dev")));
+        assert!(!matches_filter(None, Some("ops")));
+        assert!(matches_filter(None, None));
+    }
+}
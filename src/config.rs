@@ -1,18 +1,398 @@
 use crate::cli::Cli;
-use config::{Config, ConfigError, Environment, File};
+use crate::services::api::http::{ApiError, RetryPolicy};
+use crate::services::api::{
+    ApiClientConfig, BreakerPolicy, Cassette, ClientCredentialsGrant, ClientIdentity, ProxyMode,
+    ProxyOptions, RequestLimits, TlsOptions,
+};
+use crate::services::auth::{CredentialDomain, SecretSource};
+use config::{Config, ConfigError, Environment, File, Map, Source, Value, ValueKind};
 use directories::ProjectDirs;
-use serde::Deserialize;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
 use std::path::PathBuf;
+use std::time::Duration;
 use thiserror::Error;
+use url::Url;
 
-#[derive(Debug, Clone, Deserialize)]
+const ENV_PREFIX: &str = "FABREXLENS";
+const ENV_SEPARATOR: &str = "__";
+
+pub const MIN_POLL_INTERVAL_SECS: u64 = 5;
+pub const MAX_POLL_INTERVAL_SECS: u64 = 600;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct AppConfig {
     pub application_name: String,
     pub fabrex_base_url: String,
     pub gryf_base_url: String,
     pub supernode_base_url: String,
+    pub redfish_base_url: Option<String>,
     pub poll_interval_secs: u64,
+    pub fabrex: ServiceSettings,
+    pub gryf: ServiceSettings,
+    pub supernode: ServiceSettings,
+    pub redfish: ServiceSettings,
+    pub tls: ServiceTlsSettings,
+    pub proxy: ProxyConfig,
+    pub limits: LimitsConfig,
+    pub circuit_breaker: CircuitBreakerConfig,
+    /// Set from `--record` / `--replay`; not read from configuration files.
+    #[serde(skip)]
+    pub cassette: Option<Cassette>,
+}
+
+/// Client settings for one service from its `[fabrex]`, `[gryf]`, `[supernode]` or `[redfish]`
+/// table. Unset fields keep the client defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ServiceSettings {
+    /// Takes precedence over the flat `<service>_base_url` key.
+    pub base_url: Option<String>,
+    /// Request timeout in seconds.
+    pub timeout: Option<u64>,
+    pub user_agent: Option<String>,
+    /// Static headers sent with every request.
+    pub headers: BTreeMap<String, String>,
+    /// Path segment prefixed to every request path, e.g. `v1`.
+    pub api_version: Option<String>,
+    /// Retries after the first attempt for idempotent requests; `0` disables retries.
+    pub retries: Option<u32>,
+    /// Delay before the first retry in milliseconds, doubled for each retry after that.
+    pub retry_base_delay_ms: Option<u64>,
+    /// Upper bound for any retry delay in seconds.
+    pub retry_max_delay_secs: Option<u64>,
+    /// Items requested per page when following `next` cursors; unset leaves it to the server.
+    pub page_size: Option<u32>,
+    /// Most items collected from one paginated listing (default: 10000).
+    pub max_items: Option<usize>,
+    pub auth: AuthSettings,
+}
+
+/// How a service authenticates, from its `[<service>.auth]` table.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuthSettings {
+    pub mode: AuthMode,
+    /// OAuth2 token endpoint; required when `mode = "oauth2"`.
+    pub token_url: Option<String>,
+    /// Space-separated scopes requested with each token.
+    pub scope: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuthMode {
+    /// The stored API token, or basic auth with the stored username and password.
+    #[default]
+    Stored,
+    /// OAuth2 client credentials; the stored username and password are the client id and
+    /// secret.
+    Oauth2,
+}
+
+impl AuthSettings {
+    /// The token grant to use, or `None` when the service uses stored credentials directly.
+    pub fn client_credentials(&self) -> Result<Option<ClientCredentialsGrant>, ApiError> {
+        if self.mode != AuthMode::Oauth2 {
+            return Ok(None);
+        }
+        let token_url = self
+            .token_url
+            .as_deref()
+            .ok_or_else(|| ApiError::OAuth("token_url is required for oauth2".into()))?;
+        let token_url = Url::parse(token_url)?;
+        if !matches!(token_url.scheme(), "http" | "https") {
+            return Err(ApiError::OAuth(format!(
+                "unsupported token_url scheme '{}' (expected http or https)",
+                token_url.scheme()
+            )));
+        }
+        Ok(Some(ClientCredentialsGrant {
+            token_url,
+            scope: self.scope.clone().filter(|scope| !scope.trim().is_empty()),
+        }))
+    }
+}
+
+impl ServiceSettings {
+    /// Applies these settings on top of `config`.
+    pub fn apply(&self, mut config: ApiClientConfig) -> Result<ApiClientConfig, ApiError> {
+        if let Some(secs) = self.timeout {
+            config = config.with_timeout(Duration::from_secs(secs.max(1)));
+        }
+        if let Some(agent) = &self.user_agent {
+            config = config.with_user_agent(agent.clone());
+        }
+        if !self.headers.is_empty() {
+            config = config.with_headers(self.header_map()?);
+        }
+        if let Some(version) = &self.api_version {
+            let version = version.trim_matches('/');
+            if !version.is_empty() {
+                config = config.with_api_version(version);
+            }
+        }
+        config.retry = self.retry_policy(config.retry);
+        let mut page_limits = config.page_limits.clone();
+        if let Some(page_size) = self.page_size {
+            page_limits.page_size = Some(page_size.max(1));
+        }
+        if let Some(max_items) = self.max_items {
+            page_limits.max_items = max_items.max(1);
+        }
+        Ok(config.with_page_limits(page_limits))
+    }
+
+    fn header_map(&self) -> Result<HeaderMap, ApiError> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| ApiError::Header(format!("'{name}' is not a valid header name")))?;
+            let value = HeaderValue::from_str(value)
+                .map_err(|_| ApiError::Header(format!("value for '{name}' is not valid")))?;
+            headers.insert(name, value);
+        }
+        Ok(headers)
+    }
+
+    fn retry_policy(&self, mut policy: RetryPolicy) -> RetryPolicy {
+        if let Some(retries) = self.retries {
+            policy.max_attempts = retries.saturating_add(1);
+        }
+        if let Some(ms) = self.retry_base_delay_ms {
+            policy.base_delay = Duration::from_millis(ms);
+        }
+        if let Some(secs) = self.retry_max_delay_secs {
+            policy.max_delay = Duration::from_secs(secs);
+        }
+        policy
+    }
+}
+
+/// Per-service circuit breaker settings from `[circuit_breaker]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failed calls that open a service's circuit.
+    pub failure_threshold: u32,
+    /// Seconds an open circuit fails fast before probing the service again.
+    pub cooldown_secs: u64,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        let policy = BreakerPolicy::default();
+        Self {
+            failure_threshold: policy.failure_threshold,
+            cooldown_secs: policy.cooldown.as_secs(),
+        }
+    }
+}
+
+impl CircuitBreakerConfig {
+    pub fn policy(&self) -> BreakerPolicy {
+        BreakerPolicy {
+            failure_threshold: self.failure_threshold.max(1),
+            cooldown: Duration::from_secs(self.cooldown_secs.max(1)),
+        }
+    }
+}
+
+/// Global proxy settings from `[proxy]`, overridable per service in `[proxy.<service>]`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProxyConfig {
+    pub url: Option<String>,
+    pub username: Option<String>,
+    /// Preferably a `keyring:<service>/<scope>` reference, which also supplies the username.
+    pub password: Option<String>,
+    /// Comma-separated hosts, domains or CIDR ranges that bypass the proxy.
+    pub no_proxy: Option<String>,
+    pub fabrex: ProxySettings,
+    pub gryf: ProxySettings,
+    pub supernode: ProxySettings,
+    pub redfish: ProxySettings,
+}
+
+/// Per-service proxy override.
+///
+/// A service `url` replaces the global proxy together with its credentials; `no_proxy` falls
+/// back to the global list when unset. `direct = true` bypasses every proxy for the service.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProxySettings {
+    pub url: Option<String>,
+    pub username: Option<String>,
+    /// Preferably a `keyring:<service>/<scope>` reference, which also supplies the username.
+    pub password: Option<String>,
+    pub no_proxy: Option<String>,
+    pub direct: bool,
+}
+
+impl ProxyConfig {
+    pub fn get(&self, domain: &CredentialDomain) -> &ProxySettings {
+        match domain {
+            CredentialDomain::FabreX => &self.fabrex,
+            CredentialDomain::Gryf => &self.gryf,
+            CredentialDomain::Supernode => &self.supernode,
+            CredentialDomain::Redfish => &self.redfish,
+        }
+    }
+
+    /// Resolves the route for `domain`; without any proxy settings the environment applies.
+    pub fn mode(&self, domain: &CredentialDomain) -> Result<ProxyMode, ApiError> {
+        let service = self.get(domain);
+        if service.direct {
+            return Ok(ProxyMode::Direct);
+        }
+
+        let (url, username, password) = match &service.url {
+            Some(url) => (url, &service.username, &service.password),
+            None => match &self.url {
+                Some(url) => (url, &self.username, &self.password),
+                None => return Ok(ProxyMode::System),
+            },
+        };
+        let mut options = ProxyOptions::new(url)?;
+        options.username = username.clone();
+        options.password = password.as_deref().map(SecretSource::parse).transpose()?;
+        options.no_proxy = service.no_proxy.clone().or_else(|| self.no_proxy.clone());
+        Ok(ProxyMode::Proxy(options))
+    }
+}
+
+/// Global request limits from `[limits]`, overridable per service in `[limits.<service>]`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LimitsConfig {
+    pub max_in_flight: Option<usize>,
+    pub requests_per_second: Option<f64>,
+    pub burst: Option<u32>,
+    pub fabrex: LimitSettings,
+    pub gryf: LimitSettings,
+    pub supernode: LimitSettings,
+    pub redfish: LimitSettings,
+}
+
+/// Per-service request limits; each unset field falls back to the global value.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LimitSettings {
+    pub max_in_flight: Option<usize>,
+    pub requests_per_second: Option<f64>,
+    pub burst: Option<u32>,
+}
+
+impl LimitsConfig {
+    pub fn get(&self, domain: &CredentialDomain) -> &LimitSettings {
+        match domain {
+            CredentialDomain::FabreX => &self.fabrex,
+            CredentialDomain::Gryf => &self.gryf,
+            CredentialDomain::Supernode => &self.supernode,
+            CredentialDomain::Redfish => &self.redfish,
+        }
+    }
+
+    pub fn limits(&self, domain: &CredentialDomain) -> Result<RequestLimits, ApiError> {
+        let service = self.get(domain);
+        let limits = RequestLimits {
+            max_in_flight: service.max_in_flight.or(self.max_in_flight),
+            requests_per_second: service.requests_per_second.or(self.requests_per_second),
+            burst: service.burst.or(self.burst),
+        };
+        limits.validate()?;
+        Ok(limits)
+    }
+}
+
+/// TLS settings for each service, read from `[tls.<service>]` tables.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ServiceTlsSettings {
+    pub fabrex: TlsSettings,
+    pub gryf: TlsSettings,
+    pub supernode: TlsSettings,
+    pub redfish: TlsSettings,
+}
+
+impl ServiceTlsSettings {
+    pub fn get(&self, domain: &CredentialDomain) -> &TlsSettings {
+        match domain {
+            CredentialDomain::FabreX => &self.fabrex,
+            CredentialDomain::Gryf => &self.gryf,
+            CredentialDomain::Supernode => &self.supernode,
+            CredentialDomain::Redfish => &self.redfish,
+        }
+    }
+
+    /// Services whose server certificates are accepted without verification.
+    pub fn insecure_domains(&self) -> Vec<CredentialDomain> {
+        CredentialDomain::ALL
+            .into_iter()
+            .filter(|domain| self.get(domain).insecure_skip_verify)
+            .collect()
+    }
+}
+
+/// CA bundle, client identity and verification settings for one service.
+///
+/// The client identity is either `client_cert` plus `client_key` (PEM; the key may live in the
+/// certificate file) or `client_pkcs12` with an optional `client_pkcs12_password`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TlsSettings {
+    pub ca_bundle: Option<PathBuf>,
+    pub client_cert: Option<PathBuf>,
+    pub client_key: Option<PathBuf>,
+    pub client_pkcs12: Option<PathBuf>,
+    /// Preferably a `keyring:<service>/<scope>` reference rather than the password itself.
+    pub client_pkcs12_password: Option<String>,
+    /// SHA-256 fingerprint of the server certificate as hex, optionally `:`-separated.
+    pub pinned_sha256: Option<String>,
+    pub insecure_skip_verify: bool,
+}
+
+impl TlsSettings {
+    pub fn to_options(&self) -> Result<TlsOptions, ApiError> {
+        let client_identity = match (&self.client_cert, &self.client_key, &self.client_pkcs12) {
+            (None, None, None) => None,
+            (Some(cert), key, None) => Some(ClientIdentity::Pem {
+                cert: cert.clone(),
+                key: key.clone().unwrap_or_else(|| cert.clone()),
+            }),
+            (None, None, Some(path)) => Some(ClientIdentity::Pkcs12 {
+                path: path.clone(),
+                password: SecretSource::parse(
+                    self.client_pkcs12_password.as_deref().unwrap_or_default(),
+                )?,
+            }),
+            (None, Some(_), None) => {
+                return Err(ApiError::Tls(
+                    "client_key is set without client_cert".into(),
+                ))
+            }
+            (_, _, Some(_)) => {
+                return Err(ApiError::Tls(
+                    "client_pkcs12 cannot be combined with client_cert or client_key".into(),
+                ))
+            }
+        };
+        let pinned_sha256 = self
+            .pinned_sha256
+            .as_deref()
+            .map(TlsOptions::parse_fingerprint)
+            .transpose()?;
+
+        Ok(TlsOptions {
+            ca_bundle: self.ca_bundle.clone(),
+            client_identity,
+            pinned_sha256,
+            insecure_skip_verify: self.insecure_skip_verify,
+        })
+    }
 }
 
 impl Default for AppConfig {
@@ -22,7 +402,17 @@ impl Default for AppConfig {
             fabrex_base_url: "https://api.gigaio.com/fabrexfleet".to_string(),
             gryf_base_url: "https://api.gigaio.com/gryf".to_string(),
             supernode_base_url: "https://api.gigaio.com/supernodes".to_string(),
+            redfish_base_url: None,
             poll_interval_secs: 15,
+            fabrex: ServiceSettings::default(),
+            gryf: ServiceSettings::default(),
+            supernode: ServiceSettings::default(),
+            redfish: ServiceSettings::default(),
+            tls: ServiceTlsSettings::default(),
+            proxy: ProxyConfig::default(),
+            limits: LimitsConfig::default(),
+            circuit_breaker: CircuitBreakerConfig::default(),
+            cassette: None,
         }
     }
 }
@@ -31,10 +421,95 @@ impl Default for AppConfig {
 pub enum AppConfigError {
     #[error("configuration error: {0}")]
     Config(#[from] ConfigError),
+    #[error("no base URL configured for {0}")]
+    MissingBaseUrl(CredentialDomain),
+    #[error("invalid {domain} client settings: {source}")]
+    Client {
+        domain: CredentialDomain,
+        source: ApiError,
+    },
+}
+
+/// Where the effective value of a configuration key was read from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", content = "location", rename_all = "snake_case")]
+pub enum ConfigSource {
+    Default,
+    Profile(PathBuf),
+    File(PathBuf),
+    Environment(String),
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigSource::Default => write!(f, "built-in default"),
+            ConfigSource::Profile(path) => write!(f, "profile {}", path.display()),
+            ConfigSource::File(path) => write!(f, "file {}", path.display()),
+            ConfigSource::Environment(variable) => write!(f, "env {variable}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct KeyProvenance {
+    pub source: ConfigSource,
+    /// Lower-priority sources that also set this key, most recent first.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<ConfigSource>,
+}
+
+/// Effective configuration together with the source of every key that was set.
+#[derive(Debug, Clone)]
+pub struct LoadedConfig {
+    pub config: AppConfig,
+    pub provenance: BTreeMap<String, KeyProvenance>,
+}
+
+impl LoadedConfig {
+    /// Keys supplied by a file or the environment that `AppConfig` does not recognise.
+    pub fn unknown_keys(&self) -> Vec<&str> {
+        let known = AppConfig::known_keys();
+        self.provenance
+            .keys()
+            .filter(|key| !known.contains(key) && !is_header_key(key))
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigProblem {
+    pub key: String,
+    pub message: String,
+}
+
+#[derive(Debug)]
+enum LayerKind {
+    Profile(PathBuf),
+    File(PathBuf),
+    Environment,
+}
+
+impl LayerKind {
+    fn source_for(&self, key: &str) -> ConfigSource {
+        match self {
+            LayerKind::Profile(path) => ConfigSource::Profile(path.clone()),
+            LayerKind::File(path) => ConfigSource::File(path.clone()),
+            LayerKind::Environment => ConfigSource::Environment(format!(
+                "{ENV_PREFIX}{ENV_SEPARATOR}{}",
+                key.replace('.', ENV_SEPARATOR).to_uppercase()
+            )),
+        }
+    }
 }
 
 impl AppConfig {
     pub fn load(cli: &Cli) -> Result<Self, AppConfigError> {
+        Ok(Self::load_with_provenance(cli)?.config)
+    }
+
+    pub fn load_with_provenance(cli: &Cli) -> Result<LoadedConfig, AppConfigError> {
         let defaults = Self::default();
         let mut builder = Config::builder()
             .set_default("application_name", defaults.application_name.clone())?
@@ -43,23 +518,305 @@ impl AppConfig {
             .set_default("supernode_base_url", defaults.supernode_base_url.clone())?
             .set_default("poll_interval_secs", defaults.poll_interval_secs)?;
 
+        let layers = Self::layers(cli);
+        for (_, source) in &layers {
+            builder = builder.add_source(vec![source.clone_into_box()]);
+        }
+
+        let built = builder.build()?;
+        let config = built.try_deserialize::<AppConfig>()?;
+
+        let mut provenance: BTreeMap<String, KeyProvenance> = Self::known_keys()
+            .into_iter()
+            .map(|key| {
+                let entry = KeyProvenance {
+                    source: ConfigSource::Default,
+                    overrides: Vec::new(),
+                };
+                (key, entry)
+            })
+            .collect();
+
+        for (kind, source) in &layers {
+            let mut keys = Vec::new();
+            flatten_keys("", source.collect()?, &mut keys);
+            for key in keys {
+                let origin = kind.source_for(&key);
+                match provenance.get_mut(&key) {
+                    Some(entry) => {
+                        let previous = std::mem::replace(&mut entry.source, origin);
+                        entry.overrides.insert(0, previous);
+                    }
+                    None => {
+                        let entry = KeyProvenance {
+                            source: origin,
+                            overrides: Vec::new(),
+                        };
+                        provenance.insert(key, entry);
+                    }
+                }
+            }
+        }
+
+        Ok(LoadedConfig { config, provenance })
+    }
+
+    /// Checks base URLs, service sections, the poll interval, circuit breaker, TLS, proxy and
+    /// limit settings, returning every problem found.
+    pub fn validate(&self) -> Vec<ConfigProblem> {
+        let mut problems = Vec::new();
+        for domain in CredentialDomain::ALL {
+            let Some(url) = self.base_url(&domain) else {
+                continue;
+            };
+            let key = if self.service(&domain).base_url.is_some() {
+                format!("{}.base_url", domain.config_key())
+            } else {
+                format!("{}_base_url", domain.config_key())
+            };
+            match ApiClientConfig::try_from_url(url) {
+                Ok(config) if matches!(config.base_url.scheme(), "http" | "https") => {}
+                Ok(config) => problems.push(ConfigProblem {
+                    key,
+                    message: format!(
+                        "unsupported URL scheme '{}' (expected http or https)",
+                        config.base_url.scheme()
+                    ),
+                }),
+                Err(err) => problems.push(ConfigProblem {
+                    key,
+                    message: err.to_string(),
+                }),
+            }
+        }
+
+        if !(MIN_POLL_INTERVAL_SECS..=MAX_POLL_INTERVAL_SECS).contains(&self.poll_interval_secs) {
+            problems.push(ConfigProblem {
+                key: "poll_interval_secs".into(),
+                message: format!(
+                    "{} is outside the supported range {MIN_POLL_INTERVAL_SECS}-{MAX_POLL_INTERVAL_SECS}",
+                    self.poll_interval_secs
+                ),
+            });
+        }
+
+        if self.circuit_breaker.failure_threshold == 0 {
+            problems.push(ConfigProblem {
+                key: "circuit_breaker.failure_threshold".into(),
+                message: "must be at least 1".into(),
+            });
+        }
+        if self.circuit_breaker.cooldown_secs == 0 {
+            problems.push(ConfigProblem {
+                key: "circuit_breaker.cooldown_secs".into(),
+                message: "must be at least 1".into(),
+            });
+        }
+
+        for domain in CredentialDomain::ALL {
+            let service = self.service(&domain);
+            if service.timeout == Some(0) {
+                problems.push(ConfigProblem {
+                    key: format!("{}.timeout", domain.config_key()),
+                    message: "must be at least 1 second".into(),
+                });
+            }
+            for (name, value) in [
+                ("page_size", service.page_size.map(|size| size as usize)),
+                ("max_items", service.max_items),
+            ] {
+                if value == Some(0) {
+                    problems.push(ConfigProblem {
+                        key: format!("{}.{name}", domain.config_key()),
+                        message: "must be at least 1".into(),
+                    });
+                }
+            }
+            if let Err(err) = service.header_map() {
+                problems.push(ConfigProblem {
+                    key: format!("{}.headers", domain.config_key()),
+                    message: err.to_string(),
+                });
+            }
+            if domain == CredentialDomain::Redfish && service.auth.mode == AuthMode::Oauth2 {
+                problems.push(ConfigProblem {
+                    key: "redfish.auth.mode".into(),
+                    message: "oauth2 is not supported; Redfish signs in with sessions".into(),
+                });
+            } else if let Err(err) = service.auth.client_credentials() {
+                problems.push(ConfigProblem {
+                    key: format!("{}.auth", domain.config_key()),
+                    message: err.to_string(),
+                });
+            }
+            if let Err(err) = self.tls.get(&domain).to_options() {
+                problems.push(ConfigProblem {
+                    key: format!("tls.{}", domain.config_key()),
+                    message: err.to_string(),
+                });
+            }
+            if let Err(err) = self.proxy.mode(&domain) {
+                problems.push(ConfigProblem {
+                    key: format!("proxy.{}", domain.config_key()),
+                    message: err.to_string(),
+                });
+            }
+            if let Err(err) = self.limits.limits(&domain) {
+                problems.push(ConfigProblem {
+                    key: format!("limits.{}", domain.config_key()),
+                    message: err.to_string(),
+                });
+            }
+        }
+
+        problems
+    }
+
+    fn layers(cli: &Cli) -> Vec<(LayerKind, Box<dyn Source + Send + Sync>)> {
+        let mut layers: Vec<(LayerKind, Box<dyn Source + Send + Sync>)> = Vec::new();
+
         if let Some(profile) = &cli.profile {
             let profile_file_name = format!("fabrexlens.{profile}.toml");
             if let Some(path) = Self::profile_path(&profile_file_name) {
-                builder = builder.add_source(File::from(path).required(false));
+                let source = File::from(path.clone()).required(false);
+                layers.push((LayerKind::Profile(path), Box::new(source)));
             }
         }
 
         if let Some(config_path) = &cli.config {
-            builder = builder.add_source(File::from(config_path.clone()).required(true));
+            let source = File::from(config_path.clone()).required(true);
+            layers.push((LayerKind::File(config_path.clone()), Box::new(source)));
         } else if let Some(path) = Self::default_config_path() {
-            builder = builder.add_source(File::from(path).required(false));
+            let source = File::from(path.clone()).required(false);
+            layers.push((LayerKind::File(path), Box::new(source)));
         }
 
-        builder = builder.add_source(Environment::with_prefix("FABREXLENS").separator("__"));
+        let environment = Environment::with_prefix(ENV_PREFIX).separator(ENV_SEPARATOR);
+        layers.push((LayerKind::Environment, Box::new(environment)));
+        layers
+    }
 
-        let built = builder.build()?;
-        Ok(built.try_deserialize::<AppConfig>()?)
+    fn known_keys() -> Vec<String> {
+        Self::default().flattened().into_keys().collect()
+    }
+
+    /// Effective values keyed by their dotted path, e.g. `tls.redfish.insecure_skip_verify`.
+    pub fn flattened(&self) -> BTreeMap<String, serde_json::Value> {
+        let mut values = BTreeMap::new();
+        if let Ok(value) = serde_json::to_value(self) {
+            flatten_values("", value, &mut values);
+        }
+        values
+    }
+
+    /// Client configuration for `domain` from its service section and its TLS, proxy and limit
+    /// settings.
+    pub fn api_config(&self, domain: &CredentialDomain) -> Result<ApiClientConfig, AppConfigError> {
+        let client_error = |source| AppConfigError::Client {
+            domain: domain.clone(),
+            source,
+        };
+        let base_url = self
+            .base_url(domain)
+            .ok_or_else(|| AppConfigError::MissingBaseUrl(domain.clone()))?;
+        let tls = self.tls.get(domain).to_options().map_err(client_error)?;
+        let proxy = self.proxy.mode(domain).map_err(client_error)?;
+        let limits = self.limits.limits(domain).map_err(client_error)?;
+        let mut config = ApiClientConfig::try_from_url(base_url).map_err(client_error)?;
+        if let Some(cassette) = &self.cassette {
+            config = config.with_cassette(cassette.clone());
+        }
+        Ok(self
+            .service(domain)
+            .apply(config)
+            .map_err(client_error)?
+            .with_tls(tls)
+            .with_proxy(proxy)
+            .with_limits(limits))
+    }
+
+    pub fn auth_mode(&self, domain: &CredentialDomain) -> AuthMode {
+        self.service(domain).auth.mode
+    }
+
+    /// The OAuth2 grant for `domain`, or `None` when it uses stored credentials directly.
+    pub fn client_credentials(
+        &self,
+        domain: &CredentialDomain,
+    ) -> Result<Option<ClientCredentialsGrant>, AppConfigError> {
+        self.service(domain)
+            .auth
+            .client_credentials()
+            .map_err(|source| AppConfigError::Client {
+                domain: domain.clone(),
+                source,
+            })
+    }
+
+    /// Client configuration for `domain`'s OAuth2 token endpoint. The proxy and CA bundle are
+    /// shared with the service; its client identity, pins and headers belong to the service
+    /// host.
+    pub fn token_endpoint_config(
+        &self,
+        domain: &CredentialDomain,
+        grant: &ClientCredentialsGrant,
+    ) -> Result<ApiClientConfig, AppConfigError> {
+        let proxy = self
+            .proxy
+            .mode(domain)
+            .map_err(|source| AppConfigError::Client {
+                domain: domain.clone(),
+                source,
+            })?;
+        let tls = TlsOptions {
+            ca_bundle: self.tls.get(domain).ca_bundle.clone(),
+            ..TlsOptions::default()
+        };
+        Ok(ApiClientConfig::new(grant.token_url.clone())
+            .with_proxy(proxy)
+            .with_tls(tls))
+    }
+
+    /// The `[<service>] base_url`, falling back to the flat `<service>_base_url` key.
+    pub fn base_url(&self, domain: &CredentialDomain) -> Option<&str> {
+        let flat = match domain {
+            CredentialDomain::FabreX => Some(self.fabrex_base_url.as_str()),
+            CredentialDomain::Gryf => Some(self.gryf_base_url.as_str()),
+            CredentialDomain::Supernode => Some(self.supernode_base_url.as_str()),
+            CredentialDomain::Redfish => self.redfish_base_url.as_deref(),
+        };
+        self.service(domain).base_url.as_deref().or(flat)
+    }
+
+    /// True when requests are answered from a recorded cassette instead of the network.
+    pub fn is_replaying(&self) -> bool {
+        self.cassette.as_ref().is_some_and(Cassette::is_replay)
+    }
+
+    pub fn service(&self, domain: &CredentialDomain) -> &ServiceSettings {
+        match domain {
+            CredentialDomain::FabreX => &self.fabrex,
+            CredentialDomain::Gryf => &self.gryf,
+            CredentialDomain::Supernode => &self.supernode,
+            CredentialDomain::Redfish => &self.redfish,
+        }
+    }
+
+    /// Names of the profiles with a `fabrexlens.<name>.toml` file in the config directory.
+    pub fn available_profiles() -> Vec<String> {
+        let Some(dirs) = ProjectDirs::from("com", "DigitalDataCo", "FabreXLens") else {
+            return Vec::new();
+        };
+        let Ok(entries) = std::fs::read_dir(dirs.config_dir()) else {
+            return Vec::new();
+        };
+
+        let mut profiles: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| profile_name(&entry.file_name().to_string_lossy()).map(Into::into))
+            .collect();
+        profiles.sort();
+        profiles
     }
 
     fn default_config_path() -> Option<PathBuf> {
@@ -72,3 +829,335 @@ impl AppConfig {
             .map(|dirs| dirs.config_dir().join(file_name))
     }
 }
+
+fn profile_name(file_name: &str) -> Option<&str> {
+    file_name
+        .strip_prefix("fabrexlens.")?
+        .strip_suffix(".toml")
+        .filter(|name| !name.is_empty())
+}
+
+/// Header names under `[<service>.headers]` are free-form.
+fn is_header_key(key: &str) -> bool {
+    let mut parts = key.splitn(3, '.');
+    let service = parts.next().unwrap_or_default();
+    CredentialDomain::ALL
+        .iter()
+        .any(|domain| domain.config_key() == service)
+        && parts.next() == Some("headers")
+        && parts.next().is_some()
+}
+
+fn flatten_values(
+    prefix: &str,
+    value: serde_json::Value,
+    values: &mut BTreeMap<String, serde_json::Value>,
+) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (name, nested) in map {
+                let key = if prefix.is_empty() {
+                    name
+                } else {
+                    format!("{prefix}.{name}")
+                };
+                flatten_values(&key, nested, values);
+            }
+        }
+        other => {
+            values.insert(prefix.to_string(), other);
+        }
+    }
+}
+
+fn flatten_keys(prefix: &str, table: Map<String, Value>, keys: &mut Vec<String>) {
+    for (name, value) in table {
+        let key = if prefix.is_empty() {
+            name
+        } else {
+            format!("{prefix}.{name}")
+        };
+        match value.kind {
+            ValueKind::Table(nested) => flatten_keys(&key, nested, keys),
+            _ => keys.push(key),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::auth::CredentialKey;
+    use std::fs;
+
+    fn cli_with_config(path: PathBuf) -> Cli {
+        Cli {
+            config: Some(path),
+            profile: None,
+            headless: false,
+            once: false,
+            error_format: crate::cli::ErrorFormat::Text,
+            trace_http: None,
+            record: None,
+            replay: None,
+            command: None,
+        }
+    }
+
+    #[test]
+    fn records_file_provenance_and_unknown_keys() {
+        let path =
+            std::env::temp_dir().join(format!("fabrexlens-provenance-{}.toml", std::process::id()));
+        fs::write(
+            &path,
+            "gryf_base_url = \"https://gryf.example.com\"\n[ui]\ntheme = \"dark\"\n",
+        )
+        .unwrap();
+
+        let loaded = AppConfig::load_with_provenance(&cli_with_config(path.clone())).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.config.gryf_base_url, "https://gryf.example.com");
+        let gryf = &loaded.provenance["gryf_base_url"];
+        assert_eq!(gryf.source, ConfigSource::File(path));
+        assert_eq!(gryf.overrides, vec![ConfigSource::Default]);
+        assert_eq!(
+            loaded.provenance["fabrex_base_url"].source,
+            ConfigSource::Default
+        );
+        assert_eq!(loaded.unknown_keys(), vec!["ui.theme"]);
+    }
+
+    #[test]
+    fn service_sections_override_flat_keys_and_configure_clients() {
+        let path =
+            std::env::temp_dir().join(format!("fabrexlens-services-{}.toml", std::process::id()));
+        fs::write(
+            &path,
+            "fabrex_base_url = \"https://old.example.com\"\n\
+             [fabrex]\nbase_url = \"https://fabrex.example.com\"\ntimeout = 30\nretries = 0\n\
+             api_version = \"/v2/\"\npage_size = 50\nmax_items = 500\n[fabrex.headers]\nX-Tenant = \"lab\"\n\
+             [gryf]\nuser_agent = \"ops-dashboard\"\n\
+             [gryf.auth]\nmode = \"oauth2\"\ntoken_url = \"https://login.example.com/token\"\n\
+             scope = \"gryf.read\"\n\
+             [tls.gryf]\nca_bundle = \"internal-ca.pem\"\nclient_cert = \"client.pem\"\n",
+        )
+        .unwrap();
+
+        let loaded = AppConfig::load_with_provenance(&cli_with_config(path.clone())).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert!(loaded.unknown_keys().is_empty());
+
+        let config = loaded.config;
+        assert_eq!(
+            config.base_url(&CredentialDomain::FabreX),
+            Some("https://fabrex.example.com")
+        );
+        let fabrex = config.api_config(&CredentialDomain::FabreX).unwrap();
+        assert_eq!(fabrex.timeout, Duration::from_secs(30));
+        assert_eq!(fabrex.retry.max_attempts, 1);
+        assert_eq!(fabrex.api_version.as_deref(), Some("v2"));
+        assert_eq!(fabrex.headers["x-tenant"], "lab");
+        assert_eq!(fabrex.page_limits.page_size, Some(50));
+        assert_eq!(fabrex.page_limits.max_items, 500);
+
+        let gryf = config.api_config(&CredentialDomain::Gryf).unwrap();
+        assert_eq!(gryf.base_url.as_str(), "https://api.gigaio.com/gryf");
+        assert_eq!(gryf.user_agent, "ops-dashboard");
+        assert_eq!(gryf.timeout, Duration::from_secs(15));
+        assert_eq!(gryf.page_limits.page_size, None);
+        assert_eq!(gryf.page_limits.max_items, 10_000);
+
+        let grant = config
+            .client_credentials(&CredentialDomain::Gryf)
+            .unwrap()
+            .unwrap();
+        assert_eq!(grant.token_url.as_str(), "https://login.example.com/token");
+        assert_eq!(grant.scope.as_deref(), Some("gryf.read"));
+        let endpoint = config
+            .token_endpoint_config(&CredentialDomain::Gryf, &grant)
+            .unwrap();
+        assert_eq!(
+            endpoint.tls.ca_bundle.as_deref(),
+            Some(std::path::Path::new("internal-ca.pem"))
+        );
+        assert!(endpoint.tls.client_identity.is_none());
+        assert!(config
+            .client_credentials(&CredentialDomain::FabreX)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn validate_reports_bad_service_sections() {
+        let mut config = AppConfig::default();
+        config.gryf.auth.mode = AuthMode::Oauth2;
+        config.supernode.timeout = Some(0);
+        config.supernode.page_size = Some(0);
+        config.supernode.base_url = Some("supernodes".into());
+        config.redfish.auth.mode = AuthMode::Oauth2;
+        config
+            .redfish
+            .headers
+            .insert("Bad Header".into(), "value".into());
+
+        let keys: Vec<String> = config
+            .validate()
+            .into_iter()
+            .map(|problem| problem.key)
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                "supernode.base_url",
+                "gryf.auth",
+                "supernode.timeout",
+                "supernode.page_size",
+                "redfish.headers",
+                "redfish.auth.mode"
+            ]
+        );
+    }
+
+    #[test]
+    fn profile_names_come_from_profile_files_only() {
+        assert_eq!(profile_name("fabrexlens.dev.toml"), Some("dev"));
+        assert_eq!(profile_name("fabrexlens.toml"), None);
+        assert_eq!(profile_name("fabrexlens.dev.yaml"), None);
+        assert_eq!(profile_name("other.dev.toml"), None);
+    }
+
+    #[test]
+    fn validate_reports_bad_urls_and_interval() {
+        let config = AppConfig {
+            fabrex_base_url: "not a url".into(),
+            redfish_base_url: Some("ftp://bmc.example.com".into()),
+            poll_interval_secs: 1,
+            ..AppConfig::default()
+        };
+
+        let keys: Vec<String> = config
+            .validate()
+            .into_iter()
+            .map(|problem| problem.key)
+            .collect();
+        assert_eq!(
+            keys,
+            vec!["fabrex_base_url", "redfish_base_url", "poll_interval_secs"]
+        );
+        assert!(AppConfig::default().validate().is_empty());
+    }
+
+    #[test]
+    fn nested_tls_keys_are_known_and_validated() {
+        let path = std::env::temp_dir().join(format!("fabrexlens-tls-{}.toml", std::process::id()));
+        fs::write(
+            &path,
+            "[tls.redfish]\ninsecure_skip_verify = true\n[tls.fabrex]\nclient_key = \"key.pem\"\npinned_sha256 = \"abcd\"\n",
+        )
+        .unwrap();
+
+        let loaded = AppConfig::load_with_provenance(&cli_with_config(path.clone())).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(loaded.unknown_keys().is_empty());
+        assert_eq!(
+            loaded.config.tls.insecure_domains(),
+            vec![CredentialDomain::Redfish]
+        );
+        let keys: Vec<String> = loaded
+            .config
+            .validate()
+            .into_iter()
+            .map(|problem| problem.key)
+            .collect();
+        assert_eq!(keys, vec!["tls.fabrex"]);
+    }
+
+    #[test]
+    fn service_limits_fall_back_to_global_limits() {
+        let config = AppConfig {
+            limits: LimitsConfig {
+                requests_per_second: Some(10.0),
+                redfish: LimitSettings {
+                    max_in_flight: Some(2),
+                    requests_per_second: Some(1.0),
+                    ..LimitSettings::default()
+                },
+                gryf: LimitSettings {
+                    max_in_flight: Some(0),
+                    ..LimitSettings::default()
+                },
+                ..LimitsConfig::default()
+            },
+            ..AppConfig::default()
+        };
+
+        let fabrex = config.limits.limits(&CredentialDomain::FabreX).unwrap();
+        assert_eq!(fabrex.requests_per_second, Some(10.0));
+        assert_eq!(fabrex.max_in_flight, None);
+        let redfish = config.limits.limits(&CredentialDomain::Redfish).unwrap();
+        assert_eq!(redfish.requests_per_second, Some(1.0));
+        assert_eq!(redfish.max_in_flight, Some(2));
+
+        let problems = config.validate();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].key, "limits.gryf");
+    }
+
+    #[test]
+    fn service_proxy_overrides_global_proxy() {
+        let config = AppConfig {
+            proxy: ProxyConfig {
+                url: Some("http://bastion.example.com:3128".into()),
+                username: Some("ops".into()),
+                password: Some("secret".into()),
+                no_proxy: Some("localhost".into()),
+                redfish: ProxySettings {
+                    url: Some("socks5h://bmc-jump.example.com:1080".into()),
+                    password: Some("keyring:redfish/proxy".into()),
+                    ..ProxySettings::default()
+                },
+                gryf: ProxySettings {
+                    direct: true,
+                    ..ProxySettings::default()
+                },
+                ..ProxyConfig::default()
+            },
+            ..AppConfig::default()
+        };
+
+        let ProxyMode::Proxy(fabrex) = config.proxy.mode(&CredentialDomain::FabreX).unwrap() else {
+            panic!("expected the global proxy for FabreX");
+        };
+        assert_eq!(fabrex.url.host_str(), Some("bastion.example.com"));
+        assert_eq!(fabrex.username.as_deref(), Some("ops"));
+
+        let ProxyMode::Proxy(redfish) = config.proxy.mode(&CredentialDomain::Redfish).unwrap()
+        else {
+            panic!("expected the Redfish proxy");
+        };
+        assert_eq!(redfish.url.scheme(), "socks5h");
+        assert_eq!(redfish.username, None);
+        assert_eq!(
+            redfish.password,
+            Some(SecretSource::Keyring(CredentialKey::new(
+                CredentialDomain::Redfish,
+                "proxy"
+            )))
+        );
+        assert_eq!(redfish.no_proxy.as_deref(), Some("localhost"));
+
+        assert!(matches!(
+            config.proxy.mode(&CredentialDomain::Gryf).unwrap(),
+            ProxyMode::Direct
+        ));
+        assert!(matches!(
+            AppConfig::default()
+                .proxy
+                .mode(&CredentialDomain::Gryf)
+                .unwrap(),
+            ProxyMode::System
+        ));
+    }
+}
@@ -0,0 +1,124 @@
+use crate::app::{fetch_dashboard_snapshot, ServiceContext};
+use crate::config::AppConfig;
+use crate::services::auth::CredentialManager;
+use crate::ui::DashboardSnapshot;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::io::{self, Write};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::runtime::Runtime;
+use tokio::time;
+
+/// A single line of headless output: either a fresh snapshot or the refresh error.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum HeadlessRecord {
+    Snapshot {
+        captured_at: u64,
+        snapshot: DashboardSnapshot,
+    },
+    Error {
+        captured_at: u64,
+        error: String,
+    },
+}
+
+/// Runs the dashboard poll loop without a UI, writing one JSON line per refresh to stdout.
+///
+/// With `once` set a single snapshot is taken and a failed refresh yields a non-zero exit.
+pub fn run(config: AppConfig, once: bool) -> Result<()> {
+    let runtime = Runtime::new().context("Starting tokio runtime")?;
+    let interval = Duration::from_secs(config.poll_interval_secs.max(5));
+    let credentials = Arc::new(CredentialManager::with_default_keyring().with_interactive(false));
+    let services = ServiceContext::new(Arc::new(config), credentials);
+
+    runtime.block_on(async move {
+        if once {
+            if let Some(err) = refresh(&services).await? {
+                return Err(err.context("headless refresh failed"));
+            }
+            return Ok(());
+        }
+
+        let mut ticker = time::interval(interval);
+        let shutdown = shutdown_signal();
+        tokio::pin!(shutdown);
+
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {}
+                _ = &mut shutdown => break,
+            }
+            // A refresh can spend a while in retries and timeouts; a signal cancels it.
+            tokio::select! {
+                result = refresh(&services) => {
+                    result?;
+                }
+                _ = &mut shutdown => break,
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Fetches one snapshot and emits it; returns the refresh error, if there was one.
+async fn refresh(services: &ServiceContext) -> Result<Option<anyhow::Error>> {
+    let captured_at = unix_timestamp();
+    let (record, failure) = match fetch_dashboard_snapshot(services).await {
+        Ok(snapshot) => (
+            HeadlessRecord::Snapshot {
+                captured_at,
+                snapshot,
+            },
+            None,
+        ),
+        Err(err) => (
+            HeadlessRecord::Error {
+                captured_at,
+                error: format!("{err:#}"),
+            },
+            Some(err),
+        ),
+    };
+
+    emit(&record).context("Writing headless output")?;
+    Ok(failure)
+}
+
+fn emit(record: &HeadlessRecord) -> Result<()> {
+    let line = serde_json::to_string(record)?;
+    let mut stdout = io::stdout().lock();
+    writeln!(stdout, "{line}")?;
+    stdout.flush()?;
+    Ok(())
+}
+
+fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(unix)]
+async fn shutdown_signal() {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    match signal(SignalKind::terminate()) {
+        Ok(mut terminate) => {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {}
+                _ = terminate.recv() => {}
+            }
+        }
+        Err(_) => {
+            let _ = tokio::signal::ctrl_c().await;
+        }
+    }
+}
+
+#[cfg(not(unix))]
+async fn shutdown_signal() {
+    let _ = tokio::signal::ctrl_c().await;
+}
@@ -1,16 +1,25 @@
 mod app;
 mod cli;
+mod commands;
 mod config;
+mod headless;
 mod services;
 mod ui;
 
 use crate::cli::Command;
+use crate::services::api::http::ApiError;
+use crate::services::api::Cassette;
 use crate::services::auth::{CredentialKey, CredentialManager};
 use anyhow::Result;
-use clap::Parser;
+use clap::{CommandFactory, Parser};
+use clap_complete::CompleteEnv;
 use std::panic;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    // Answers shell completion requests (`COMPLETE=<shell> fabrexlens ...`) and exits.
+    CompleteEnv::with_factory(cli::Cli::command).complete();
 
-fn main() -> Result<()> {
     panic::set_hook(Box::new(|info| {
         eprintln!("FabreXLens panic: {info}");
         if let Some(location) = info.location() {
@@ -19,22 +28,93 @@ fn main() -> Result<()> {
     }));
 
     let cli = cli::Cli::parse();
+    if let Some(level) = cli.trace_http {
+        init_http_tracing(level);
+    }
+
+    let cassette = match open_cassette(&cli) {
+        Ok(cassette) => cassette,
+        Err(err) => return commands::errors::report(&err.into(), cli.error_format),
+    };
+    let code = match run(&cli, cassette.as_ref()) {
+        Ok(code) => code,
+        Err(err) => commands::errors::report(&err, cli.error_format),
+    };
+    if let Some(cassette) = &cassette {
+        report_cassette(cassette);
+    }
+    code
+}
+
+fn open_cassette(cli: &cli::Cli) -> Result<Option<Cassette>, ApiError> {
+    match (&cli.record, &cli.replay) {
+        (Some(dir), _) => Cassette::record(dir).map(Some),
+        (None, Some(dir)) => Cassette::replay(dir).map(Some),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Lists replayed requests that had no recorded response, so gaps in a cassette are visible.
+fn report_cassette(cassette: &Cassette) {
+    if !cassette.is_replay() {
+        eprintln!("Recorded HTTP traffic to {}", cassette.path().display());
+        return;
+    }
+    let unmatched = cassette.unmatched();
+    if !unmatched.is_empty() {
+        eprintln!(
+            "{} request(s) had no recorded response in {}:",
+            unmatched.len(),
+            cassette.path().display()
+        );
+        for request in unmatched {
+            eprintln!("  {request}");
+        }
+    }
+}
 
+/// Loads the configuration and attaches the `--record` / `--replay` cassette.
+fn load_settings(cli: &cli::Cli, cassette: Option<&Cassette>) -> Result<config::AppConfig> {
+    let mut settings = config::AppConfig::load(cli)?;
+    settings.cassette = cassette.cloned();
+    Ok(settings)
+}
+
+/// Sends `fabrexlens::http` events to stderr; other crates' tracing stays silent.
+fn init_http_tracing(level: cli::HttpTrace) {
+    use tracing_subscriber::filter::{LevelFilter, Targets};
+    use tracing_subscriber::prelude::*;
+
+    let max_level = match level {
+        cli::HttpTrace::Summary => LevelFilter::DEBUG,
+        cli::HttpTrace::Bodies => LevelFilter::TRACE,
+    };
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
+        .with(Targets::new().with_target(services::api::trace::TARGET, max_level))
+        .init();
+}
+
+fn run(cli: &cli::Cli, cassette: Option<&Cassette>) -> Result<ExitCode> {
     if let Some(command) = cli.command.clone() {
-        return handle_command(command);
+        return handle_command(cli, command, cassette);
     }
 
-    let settings = config::AppConfig::load(&cli)?;
+    let settings = load_settings(cli, cassette)?;
 
     if cli.headless {
-        println!("Headless mode is not yet available. Launching UI skipped.");
-        return Ok(());
+        headless::run(settings, cli.once)?;
+    } else {
+        app::run(settings)?;
     }
-
-    app::run(settings)
+    Ok(ExitCode::SUCCESS)
 }
 
-fn handle_command(command: Command) -> Result<()> {
+fn handle_command(
+    cli: &cli::Cli,
+    command: Command,
+    cassette: Option<&Cassette>,
+) -> Result<ExitCode> {
     match command {
         Command::AuthInit { domain, scope } => {
             let manager = CredentialManager::with_default_keyring();
@@ -46,6 +126,36 @@ fn handle_command(command: Command) -> Result<()> {
                 secret.redacted_summary()
             );
         }
+        Command::Auth { command, output } => {
+            let settings = load_settings(cli, cassette)?;
+            return commands::auth::run(settings, command, output);
+        }
+        Command::Fabrics { command, output } => {
+            let settings = load_settings(cli, cassette)?;
+            return commands::fabrics::run(settings, command, output);
+        }
+        Command::Workloads { command, output } => {
+            let settings = load_settings(cli, cassette)?;
+            return commands::workloads::run(settings, command, output);
+        }
+        Command::Endpoints { command, output } => {
+            let settings = load_settings(cli, cassette)?;
+            return commands::endpoints::run(settings, command, output);
+        }
+        Command::Nodes { command, output } => {
+            let settings = load_settings(cli, cassette)?;
+            return commands::nodes::run(settings, command, output);
+        }
+        Command::Doctor { output } => {
+            let settings = load_settings(cli, cassette)?;
+            return commands::doctor::run(settings, output);
+        }
+        Command::Completions { shell } => return commands::generate::completions(shell),
+        Command::Manpage => return commands::generate::manpage(),
+        Command::Config { command, output } => {
+            let loaded = config::AppConfig::load_with_provenance(cli)?;
+            return commands::config::run(loaded, command, output);
+        }
     }
-    Ok(())
+    Ok(ExitCode::SUCCESS)
 }
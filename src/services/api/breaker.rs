@@ -0,0 +1,175 @@
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// When a [`CircuitBreaker`] opens and how long it stays open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BreakerPolicy {
+    /// Consecutive failed calls that open the circuit.
+    pub failure_threshold: u32,
+    /// Time the circuit stays open before a single probe call is let through.
+    pub cooldown: Duration,
+}
+
+impl Default for BreakerPolicy {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Observable state of a [`CircuitBreaker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakerStatus {
+    Closed,
+    /// Calls fail immediately until the cooldown ends.
+    Open {
+        retry_in: Duration,
+    },
+    /// The cooldown has ended; the next call probes the service.
+    HalfOpen,
+}
+
+impl fmt::Display for BreakerStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BreakerStatus::Closed => write!(f, "healthy"),
+            BreakerStatus::Open { retry_in } => {
+                write!(
+                    f,
+                    "unavailable, next probe in {}s",
+                    retry_in.as_secs().max(1)
+                )
+            }
+            BreakerStatus::HalfOpen => write!(f, "probing"),
+        }
+    }
+}
+
+/// Fails calls to an unhealthy service fast instead of waiting out every timeout.
+///
+/// Shared by every client of one service so the state survives client rebuilds. A call fails
+/// when the service cannot be reached or answers with a 5xx or 429 status; errors raised before
+/// anything is sent do not count.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    policy: BreakerPolicy,
+    state: Mutex<BreakerState>,
+}
+
+#[derive(Debug, Default)]
+struct BreakerState {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    probe_started: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    pub fn new(policy: BreakerPolicy) -> Self {
+        Self {
+            policy,
+            state: Mutex::default(),
+        }
+    }
+
+    pub fn status(&self) -> BreakerStatus {
+        let state = self.lock();
+        match state.opened_at {
+            None => BreakerStatus::Closed,
+            Some(opened_at) => match self.policy.cooldown.checked_sub(opened_at.elapsed()) {
+                Some(retry_in) if !retry_in.is_zero() => BreakerStatus::Open { retry_in },
+                _ => BreakerStatus::HalfOpen,
+            },
+        }
+    }
+
+    /// Admits a call, or returns how long until the next probe while the circuit is open.
+    pub(crate) fn admit(&self) -> Result<(), Duration> {
+        let mut state = self.lock();
+        let Some(opened_at) = state.opened_at else {
+            return Ok(());
+        };
+        if let Some(retry_in) = self.policy.cooldown.checked_sub(opened_at.elapsed()) {
+            if !retry_in.is_zero() {
+                return Err(retry_in);
+            }
+        }
+        // One probe at a time; a probe whose outcome was never recorded expires after a cooldown.
+        match state.probe_started {
+            Some(started) if started.elapsed() < self.policy.cooldown => Err(Duration::ZERO),
+            _ => {
+                state.probe_started = Some(Instant::now());
+                Ok(())
+            }
+        }
+    }
+
+    pub(crate) fn record_success(&self) {
+        *self.lock() = BreakerState::default();
+    }
+
+    pub(crate) fn record_failure(&self) {
+        let mut state = self.lock();
+        state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+        if state.probe_started.take().is_some()
+            || state.consecutive_failures >= self.policy.failure_threshold
+        {
+            state.opened_at = Some(Instant::now());
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BreakerState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn opens_after_consecutive_failures_and_probes_after_cooldown() {
+        let breaker = CircuitBreaker::new(BreakerPolicy {
+            failure_threshold: 2,
+            cooldown: Duration::from_millis(30),
+        });
+
+        breaker.record_failure();
+        assert_eq!(breaker.status(), BreakerStatus::Closed);
+        breaker.record_failure();
+        assert!(matches!(breaker.status(), BreakerStatus::Open { .. }));
+        assert!(breaker.admit().is_err());
+
+        thread::sleep(Duration::from_millis(40));
+        assert_eq!(breaker.status(), BreakerStatus::HalfOpen);
+        assert!(breaker.admit().is_ok());
+        assert_eq!(breaker.admit(), Err(Duration::ZERO));
+
+        // A failed probe reopens the circuit for another cooldown.
+        breaker.record_failure();
+        assert!(matches!(breaker.status(), BreakerStatus::Open { .. }));
+
+        thread::sleep(Duration::from_millis(40));
+        assert!(breaker.admit().is_ok());
+        breaker.record_success();
+        assert_eq!(breaker.status(), BreakerStatus::Closed);
+        assert!(breaker.admit().is_ok());
+    }
+
+    #[test]
+    fn success_resets_the_failure_count() {
+        let breaker = CircuitBreaker::new(BreakerPolicy {
+            failure_threshold: 2,
+            cooldown: Duration::from_secs(30),
+        });
+        breaker.record_failure();
+        breaker.record_success();
+        breaker.record_failure();
+        assert_eq!(breaker.status(), BreakerStatus::Closed);
+    }
+}
@@ -0,0 +1,136 @@
+use reqwest::header::{
+    HeaderMap, HeaderName, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+};
+use reqwest::RequestBuilder;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use url::Url;
+
+/// Validators and decoded bodies of GET responses, shared by every client of a session.
+///
+/// Requests for a cached URL carry `If-None-Match` / `If-Modified-Since`; a `304 Not Modified`
+/// answer is then served from the stored value.
+#[derive(Default)]
+pub struct HttpCache {
+    entries: Mutex<HashMap<Url, CacheEntry>>,
+    requests: AtomicU64,
+    not_modified: AtomicU64,
+    bytes_received: AtomicU64,
+    bytes_saved: AtomicU64,
+}
+
+struct CacheEntry {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    value: Arc<Value>,
+    size: u64,
+}
+
+/// Request and byte counts accumulated since the last [`HttpCache::take_stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferStats {
+    pub requests: u64,
+    /// Requests answered with `304 Not Modified` and served from the cache.
+    pub not_modified: u64,
+    pub bytes_received: u64,
+    /// Body bytes the cache avoided downloading.
+    pub bytes_saved: u64,
+}
+
+impl fmt::Debug for HttpCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let entries = self.entries.lock().map(|map| map.len()).unwrap_or_default();
+        f.debug_struct("HttpCache")
+            .field("entries", &entries)
+            .finish_non_exhaustive()
+    }
+}
+
+impl HttpCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the counters gathered so far and starts a new period.
+    pub fn take_stats(&self) -> TransferStats {
+        TransferStats {
+            requests: self.requests.swap(0, Ordering::Relaxed),
+            not_modified: self.not_modified.swap(0, Ordering::Relaxed),
+            bytes_received: self.bytes_received.swap(0, Ordering::Relaxed),
+            bytes_saved: self.bytes_saved.swap(0, Ordering::Relaxed),
+        }
+    }
+
+    pub(crate) fn record_request(&self) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_bytes(&self, bytes: usize) {
+        self.bytes_received
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    /// Adds conditional headers when `url` has a cached response.
+    pub(crate) fn apply_validators(
+        &self,
+        url: &Url,
+        mut builder: RequestBuilder,
+    ) -> RequestBuilder {
+        let entries = match self.entries.lock() {
+            Ok(entries) => entries,
+            Err(_) => return builder,
+        };
+        if let Some(entry) = entries.get(url) {
+            if let Some(etag) = &entry.etag {
+                builder = builder.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &entry.last_modified {
+                builder = builder.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+        builder
+    }
+
+    /// The stored value for a `304 Not Modified` answer to `url`.
+    pub(crate) fn revalidated(&self, url: &Url) -> Option<Arc<Value>> {
+        let entries = self.entries.lock().ok()?;
+        let entry = entries.get(url)?;
+        self.not_modified.fetch_add(1, Ordering::Relaxed);
+        self.bytes_saved.fetch_add(entry.size, Ordering::Relaxed);
+        Some(entry.value.clone())
+    }
+
+    /// Keeps `value` for `url` when the response carried an `ETag` or `Last-Modified`.
+    pub(crate) fn store(&self, url: &Url, headers: &HeaderMap, value: Arc<Value>, size: usize) {
+        let header = |name: HeaderName| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+        let etag = header(ETAG);
+        let last_modified = header(LAST_MODIFIED);
+
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+        if etag.is_none() && last_modified.is_none() {
+            entries.remove(url);
+            return;
+        }
+        entries.insert(
+            url.clone(),
+            CacheEntry {
+                etag,
+                last_modified,
+                value,
+                size: size as u64,
+            },
+        );
+    }
+}
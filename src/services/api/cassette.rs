@@ -0,0 +1,311 @@
+use super::http::ApiError;
+use super::trace::{self, RequestRecord};
+use reqwest::header::{CONTENT_ENCODING, CONTENT_LENGTH, TRANSFER_ENCODING};
+use reqwest::Response;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use url::Url;
+
+const FILE_NAME: &str = "cassette.jsonl";
+
+/// Recorded HTTP interactions, written by `--record` and served by `--replay`.
+///
+/// The file holds one JSON interaction per line, appended as each exchange completes.
+///
+/// Requests and responses pass through the same redaction as `--trace-http`, so cassettes can
+/// be shared. Replay matches on method, path and query (in any order) and ignores the host, so
+/// traffic captured against one deployment can be replayed with another configuration.
+#[derive(Debug, Clone)]
+pub struct Cassette {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    path: PathBuf,
+    replay: bool,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    /// Record: the open cassette file.
+    writer: Option<File>,
+    /// Replay: the loaded interactions.
+    interactions: Vec<Interaction>,
+    /// Replay: interaction indexes per request key and how many of them were served.
+    queues: HashMap<String, (Vec<usize>, usize)>,
+    unmatched: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    request: RecordedRequest,
+    response: RecordedResponse,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedRequest {
+    method: String,
+    url: Url,
+    headers: Vec<(String, String)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl Cassette {
+    /// Starts an empty cassette in `dir`, replacing any previous recording.
+    pub fn record(dir: &Path) -> Result<Self, ApiError> {
+        fs::create_dir_all(dir)
+            .map_err(|err| ApiError::Cassette(format!("cannot create {}: {err}", dir.display())))?;
+        let path = dir.join(FILE_NAME);
+        let writer = File::create(&path)
+            .map_err(|err| ApiError::Cassette(format!("cannot write {}: {err}", path.display())))?;
+        let state = State {
+            writer: Some(writer),
+            ..State::default()
+        };
+        Ok(Self::new(path, false, state))
+    }
+
+    /// Loads the cassette in `dir` for replay.
+    pub fn replay(dir: &Path) -> Result<Self, ApiError> {
+        let path = dir.join(FILE_NAME);
+        let contents = fs::read_to_string(&path)
+            .map_err(|err| ApiError::Cassette(format!("cannot read {}: {err}", path.display())))?;
+        let interactions = contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(number, line)| {
+                serde_json::from_str::<Interaction>(line).map_err(|err| {
+                    ApiError::Cassette(format!(
+                        "invalid {} line {}: {err}",
+                        path.display(),
+                        number + 1
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut queues: HashMap<String, (Vec<usize>, usize)> = HashMap::new();
+        for (index, interaction) in interactions.iter().enumerate() {
+            let key = request_key(&interaction.request.method, &interaction.request.url);
+            queues.entry(key).or_default().0.push(index);
+        }
+        let state = State {
+            interactions,
+            queues,
+            ..State::default()
+        };
+        Ok(Self::new(path, true, state))
+    }
+
+    fn new(path: PathBuf, replay: bool, state: State) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                path,
+                replay,
+                state: Mutex::new(state),
+            }),
+        }
+    }
+
+    pub fn is_replay(&self) -> bool {
+        self.inner.replay
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.inner.path
+    }
+
+    /// Requests that reached replay without a recorded response, as `METHOD path?query`.
+    pub fn unmatched(&self) -> Vec<String> {
+        self.lock().unmatched.clone()
+    }
+
+    /// The next recorded response for `request`. Repeated requests are answered in recorded
+    /// order; once those run out the last response is served again.
+    pub(crate) fn respond(&self, request: &RequestRecord) -> Result<Response, ApiError> {
+        let key = request_key(request.method.as_str(), &request.url);
+        let mut state = self.lock();
+        let index = match state.queues.get_mut(&key) {
+            Some((indexes, served)) => {
+                let index = indexes[(*served).min(indexes.len() - 1)];
+                *served += 1;
+                index
+            }
+            None => {
+                if !state.unmatched.contains(&key) {
+                    state.unmatched.push(key.clone());
+                }
+                return Err(ApiError::NotRecorded(key));
+            }
+        };
+
+        let recorded = &state.interactions[index].response;
+        let mut response = http::Response::builder().status(recorded.status);
+        for (name, value) in &recorded.headers {
+            response = response.header(name, value);
+        }
+        response
+            .body(recorded.body.clone())
+            .map(Response::from)
+            .map_err(|err| ApiError::Cassette(format!("invalid recorded response: {err}")))
+    }
+
+    /// Appends the redacted exchange to the cassette and returns an equivalent response.
+    pub(crate) async fn capture(
+        &self,
+        request: &RequestRecord,
+        response: Response,
+    ) -> Result<Response, ApiError> {
+        let status = response.status();
+        let mut headers = response.headers().clone();
+        for name in [CONTENT_LENGTH, CONTENT_ENCODING, TRANSFER_ENCODING] {
+            headers.remove(name);
+        }
+        let body = response.text().await.map_err(ApiError::Request)?;
+
+        let interaction = Interaction {
+            request: RecordedRequest {
+                method: request.method.to_string(),
+                url: request.url.clone(),
+                headers: request.headers.clone(),
+                body: request.body.clone(),
+            },
+            response: RecordedResponse {
+                status: status.as_u16(),
+                headers: trace::redact_headers(&headers),
+                body: trace::redact_body(&body),
+            },
+        };
+        self.append(&interaction)?;
+
+        // The caller still sees the real headers and body; only the cassette is redacted.
+        let mut replayed = http::Response::new(body);
+        *replayed.status_mut() = status;
+        *replayed.headers_mut() = headers;
+        Ok(Response::from(replayed))
+    }
+
+    /// Writes `interaction` as one line; the lock is only held for that single write.
+    fn append(&self, interaction: &Interaction) -> Result<(), ApiError> {
+        let mut line =
+            serde_json::to_vec(interaction).map_err(|err| ApiError::Cassette(err.to_string()))?;
+        line.push(b'\n');
+        let mut state = self.lock();
+        let writer = state
+            .writer
+            .as_mut()
+            .ok_or_else(|| ApiError::Cassette("cassette is not recording".into()))?;
+        writer.write_all(&line).map_err(|err| {
+            ApiError::Cassette(format!("cannot write {}: {err}", self.inner.path.display()))
+        })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.inner
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// `METHOD path?query` with query pairs sorted so parameter order does not matter.
+fn request_key(method: &str, url: &Url) -> String {
+    let mut pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    pairs.sort();
+    let mut key = format!("{} {}", method.to_ascii_uppercase(), url.path());
+    if !pairs.is_empty() {
+        let query = url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(pairs)
+            .finish();
+        key.push('?');
+        key.push_str(&query);
+    }
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::api::{ApiClientConfig, AuthContext, HttpClient};
+    use httpmock::prelude::*;
+
+    #[tokio::test]
+    async fn replays_recorded_responses_without_network() {
+        let dir = std::env::temp_dir().join(format!("fabrexlens-cassette-{}", std::process::id()));
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/fabrics").query_param("limit", "2");
+            then.status(200)
+                .json_body(serde_json::json!({ "items": [{ "id": "fab-1" }], "token": "t0p" }));
+        });
+
+        let recorder = Cassette::record(&dir).unwrap();
+        let config = ApiClientConfig::try_from_url(&server.url("/"))
+            .unwrap()
+            .with_cassette(recorder);
+        let live = HttpClient::new(config)
+            .unwrap()
+            .get_json::<serde_json::Value>("/fabrics?limit=2", Some(&AuthContext::bearer("s3cr3t")))
+            .await
+            .unwrap();
+        assert_eq!(live.data["token"], "t0p");
+
+        let recorded = fs::read_to_string(dir.join(FILE_NAME)).unwrap();
+        assert!(!recorded.contains("s3cr3t"));
+        assert!(!recorded.contains("t0p"));
+        assert_eq!(recorded.lines().count(), 1);
+
+        // Nothing listens on the discard port; every answer must come from the cassette.
+        let player = Cassette::replay(&dir).unwrap();
+        let config = ApiClientConfig::try_from_url("http://127.0.0.1:9/")
+            .unwrap()
+            .with_cassette(player.clone());
+        let client = HttpClient::new(config).unwrap();
+        let replayed = client
+            .get_json::<serde_json::Value>("/fabrics?limit=2", None)
+            .await
+            .unwrap();
+        assert_eq!(replayed.data["items"], live.data["items"]);
+
+        let err = client
+            .get_json::<serde_json::Value>("/workloads", None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err.last_attempt(), ApiError::NotRecorded(_)));
+        assert_eq!(player.unmatched(), vec!["GET /workloads"]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn request_keys_ignore_host_and_query_order() {
+        let recorded = Url::parse("https://prod.example.com/fabrics?limit=50&cursor=abc").unwrap();
+        let replayed = Url::parse("http://localhost:8080/fabrics?cursor=abc&limit=50").unwrap();
+        assert_eq!(request_key("GET", &recorded), request_key("get", &replayed));
+        assert_eq!(
+            request_key("GET", &recorded),
+            "GET /fabrics?cursor=abc&limit=50"
+        );
+        assert_ne!(
+            request_key("GET", &recorded),
+            request_key("DELETE", &recorded)
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use super::http::{ApiClientConfig, ApiError, AuthContext, HttpClient, Paginated, Pagination};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::sync::Arc;
 
@@ -23,11 +23,7 @@ impl FabrexClient {
     }
 
     pub async fn list_fabrics(&self) -> Result<Vec<FabrexFabric>, ApiError> {
-        let response = self
-            .http
-            .get_json::<Paginated<FabrexFabric>>("/fabrics", self.auth.as_ref())
-            .await?;
-        Ok(response.data.items)
+        self.http.get_all("/fabrics", self.auth.as_ref()).await
     }
 
     pub async fn list_fabrics_paginated(&self) -> Result<Paginated<FabrexFabric>, ApiError> {
@@ -38,7 +34,12 @@ impl FabrexClient {
         Ok(response.data)
     }
 
-    pub async fn list_endpoints(
+    pub async fn list_endpoints(&self, fabric_id: &str) -> Result<Vec<FabrexEndpoint>, ApiError> {
+        let path = format!("/fabrics/{fabric_id}/endpoints");
+        self.http.get_all(&path, self.auth.as_ref()).await
+    }
+
+    pub async fn list_endpoints_paginated(
         &self,
         fabric_id: &str,
         pagination: Option<Pagination>,
@@ -82,7 +83,7 @@ impl FabrexClient {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FabrexFabric {
     pub id: String,
@@ -92,7 +93,7 @@ pub struct FabrexFabric {
     pub description: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FabrexEndpoint {
     pub id: String,
@@ -104,7 +105,7 @@ pub struct FabrexEndpoint {
     pub status: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FabrexUsage {
     pub fabric_id: String,
@@ -115,14 +116,14 @@ pub struct FabrexUsage {
     pub alerts: Vec<UsageAlert>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UsageAlert {
     pub severity: String,
     pub message: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FabrexReassignmentResult {
     pub request_id: String,
@@ -191,7 +192,7 @@ mod tests {
             cursor: Some("next-cursor".into()),
         };
         let endpoints = client
-            .list_endpoints("fab-1", Some(pagination))
+            .list_endpoints_paginated("fab-1", Some(pagination))
             .await
             .unwrap();
 
@@ -1,5 +1,5 @@
 use super::http::{ApiClientConfig, ApiError, AuthContext, HttpClient, Paginated, Pagination};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::sync::Arc;
 
@@ -23,11 +23,7 @@ impl GryfClient {
     }
 
     pub async fn list_workloads(&self) -> Result<Vec<GryfWorkload>, ApiError> {
-        let response = self
-            .http
-            .get_json::<Paginated<GryfWorkload>>("/workloads", self.auth.as_ref())
-            .await?;
-        Ok(response.data.items)
+        self.http.get_all("/workloads", self.auth.as_ref()).await
     }
 
     pub async fn list_workloads_paginated(
@@ -73,7 +69,7 @@ impl GryfClient {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GryfWorkload {
     pub id: String,
@@ -83,7 +79,7 @@ pub struct GryfWorkload {
     pub owner: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GryfWorkloadDetail {
     #[serde(flatten)]
@@ -94,7 +90,7 @@ pub struct GryfWorkloadDetail {
     pub metrics: Vec<GryfMetric>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GryfTask {
     pub id: String,
@@ -102,7 +98,7 @@ pub struct GryfTask {
     pub status: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GryfMetric {
     pub key: String,
@@ -111,7 +107,7 @@ pub struct GryfMetric {
     pub unit: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GryfReassignmentResult {
     pub request_id: String,
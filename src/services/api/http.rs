@@ -1,8 +1,26 @@
-use reqwest::{header::HeaderMap, Client, Method, RequestBuilder, StatusCode};
+use super::breaker::CircuitBreaker;
+use super::cache::HttpCache;
+use super::cassette::Cassette;
+use super::limit::{HostLimiter, RequestLimits};
+use super::metrics::ServiceMetrics;
+use super::problem::ErrorDetails;
+use super::proxy::ProxyMode;
+use super::tls::TlsOptions;
+use super::trace::{self, RequestRecord};
+use crate::services::auth::AuthError;
+use futures_util::future::BoxFuture;
+use futures_util::stream::{self, Stream, TryStreamExt};
+use rand::Rng;
+use reqwest::header::{HeaderMap, CONTENT_TYPE, RETRY_AFTER};
+use reqwest::{Client, Method, Request, RequestBuilder, Response, StatusCode};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
-use std::time::Duration;
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
 use thiserror::Error;
+use tokio::time;
 use url::Url;
 
 #[derive(Debug, Clone)]
@@ -10,6 +28,136 @@ pub struct ApiClientConfig {
     pub base_url: Url,
     pub timeout: Duration,
     pub user_agent: String,
+    /// Static headers sent with every request.
+    pub headers: HeaderMap,
+    /// Path segment prefixed to every request path, e.g. `v1` turns `/fabrics` into `/v1/fabrics`.
+    pub api_version: Option<String>,
+    pub retry: RetryPolicy,
+    pub page_limits: PageLimits,
+    pub tls: TlsOptions,
+    pub proxy: ProxyMode,
+    pub limits: RequestLimits,
+    /// Fails calls fast while the service is unhealthy when set.
+    pub breaker: Option<Arc<CircuitBreaker>>,
+    /// Enables conditional GETs and transfer counters when set.
+    pub cache: Option<Arc<HttpCache>>,
+    /// Records every exchange to, or answers every request from, a cassette when set.
+    pub cassette: Option<Cassette>,
+    /// Collects per-route latency, status and byte counts when set.
+    pub metrics: Option<ServiceMetrics>,
+    /// Replaces rejected credentials and retries the request once when set.
+    pub reauth: Option<Arc<dyn Reauthenticate>>,
+}
+
+/// Obtains fresh credentials after a service rejects the current ones with `401` or `419`.
+pub trait Reauthenticate: fmt::Debug + Send + Sync {
+    /// Fresh auth for the service, or `None` when none could be obtained. `status` and
+    /// `rejected` are the rejection that triggered the call and the auth it was sent with, so
+    /// concurrent rejections of the same credentials can share one renewal.
+    fn reauthenticate<'a>(
+        &'a self,
+        status: StatusCode,
+        rejected: &'a AuthContext,
+    ) -> BoxFuture<'a, Option<AuthContext>>;
+}
+
+/// `401 Unauthorized`, or the non-standard `419` some gateways answer expired sessions with.
+fn is_auth_rejection(status: StatusCode) -> bool {
+    status == StatusCode::UNAUTHORIZED || status.as_u16() == 419
+}
+
+/// Page size and safety cap applied when following `next` cursors.
+#[derive(Debug, Clone)]
+pub struct PageLimits {
+    /// Items requested per page; `None` leaves the page size to the server.
+    pub page_size: Option<u32>,
+    /// Collecting stops with [`ApiError::TooManyItems`] once more items than this arrive.
+    pub max_items: usize,
+}
+
+impl Default for PageLimits {
+    fn default() -> Self {
+        Self {
+            page_size: None,
+            max_items: 10_000,
+        }
+    }
+}
+
+/// When and how often a failed request is sent again.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total attempts including the first; `1` disables retries.
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled for each attempt after that.
+    pub base_delay: Duration,
+    /// Upper bound for any single delay, including one requested by `Retry-After`.
+    pub max_delay: Duration,
+    /// Fraction of each backoff delay (0.0-1.0) randomly added or removed.
+    pub jitter: f64,
+    pub retryable_statuses: Vec<StatusCode>,
+    pub retryable_methods: Vec<Method>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(30),
+            jitter: 0.2,
+            retryable_statuses: vec![
+                StatusCode::REQUEST_TIMEOUT,
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+            retryable_methods: vec![Method::GET, Method::DELETE],
+        }
+    }
+}
+
+impl RetryPolicy {
+    fn retries_method(&self, method: &Method) -> bool {
+        self.retryable_methods.contains(method)
+    }
+
+    fn retries_status(&self, status: StatusCode) -> bool {
+        self.retryable_statuses.contains(&status)
+    }
+
+    /// Delay before attempt `attempt + 1`, preferring the server's `Retry-After` when given.
+    fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        if let Some(requested) = retry_after {
+            return requested.min(self.max_delay);
+        }
+
+        let exponent = attempt.saturating_sub(1).min(16);
+        let backoff = self.base_delay.saturating_mul(1 << exponent);
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        let factor = if jitter > 0.0 {
+            rand::thread_rng().gen_range(1.0 - jitter..=1.0 + jitter)
+        } else {
+            1.0
+        };
+        backoff.mul_f64(factor).min(self.max_delay)
+    }
+}
+
+/// Parses a `Retry-After` header given either as delta-seconds or as an HTTP date.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let at = httpdate::parse_http_date(value).ok()?;
+    Some(at.duration_since(SystemTime::now()).unwrap_or_default())
+}
+
+/// Transport failures worth another attempt; builder and decode errors will not change.
+fn is_transient(err: &reqwest::Error) -> bool {
+    err.is_timeout() || err.is_connect() || err.is_request() || err.is_body()
 }
 
 impl ApiClientConfig {
@@ -23,6 +171,18 @@ impl ApiClientConfig {
             base_url,
             timeout: Duration::from_secs(15),
             user_agent: format!("FabreXLens/{}", env!("CARGO_PKG_VERSION")),
+            headers: HeaderMap::new(),
+            api_version: None,
+            retry: RetryPolicy::default(),
+            page_limits: PageLimits::default(),
+            tls: TlsOptions::default(),
+            proxy: ProxyMode::default(),
+            limits: RequestLimits::default(),
+            breaker: None,
+            cache: None,
+            cassette: None,
+            metrics: None,
+            reauth: None,
         }
     }
 
@@ -35,34 +195,136 @@ impl ApiClientConfig {
         self.user_agent = agent.into();
         self
     }
+
+    pub fn with_headers(mut self, headers: HeaderMap) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    pub fn with_api_version(mut self, version: impl Into<String>) -> Self {
+        self.api_version = Some(version.into());
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn with_page_limits(mut self, page_limits: PageLimits) -> Self {
+        self.page_limits = page_limits;
+        self
+    }
+
+    pub fn with_tls(mut self, tls: TlsOptions) -> Self {
+        self.tls = tls;
+        self
+    }
+
+    pub fn with_proxy(mut self, proxy: ProxyMode) -> Self {
+        self.proxy = proxy;
+        self
+    }
+
+    pub fn with_limits(mut self, limits: RequestLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn with_breaker(mut self, breaker: Arc<CircuitBreaker>) -> Self {
+        self.breaker = Some(breaker);
+        self
+    }
+
+    pub fn with_cache(mut self, cache: Arc<HttpCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub fn with_cassette(mut self, cassette: Cassette) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    pub fn with_metrics(mut self, metrics: ServiceMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    pub fn with_reauth(mut self, reauth: Arc<dyn Reauthenticate>) -> Self {
+        self.reauth = Some(reauth);
+        self
+    }
 }
 
 #[derive(Clone)]
 pub struct HttpClient {
     client: Client,
     config: ApiClientConfig,
+    limiter: Option<Arc<HostLimiter>>,
+    /// The caller's auth that was rejected and the auth [`Reauthenticate`] replaced it with.
+    /// Only requests made with that same caller auth are sent with the replacement.
+    refreshed_auth: Arc<Mutex<Option<(AuthContext, AuthContext)>>>,
 }
 
 impl HttpClient {
     pub fn new(config: ApiClientConfig) -> Result<Self, ApiError> {
-        let client = Client::builder()
+        let mut builder = Client::builder()
             .timeout(config.timeout)
             .user_agent(config.user_agent.clone())
-            .build()
-            .map_err(ApiError::Request)?;
+            .default_headers(config.headers.clone());
+        builder = config.proxy.apply(builder)?;
+        if !config.tls.is_default() {
+            builder = builder.use_preconfigured_tls(config.tls.client_config()?);
+        }
+        let client = builder.build().map_err(ApiError::Request)?;
+        config.limits.validate()?;
+        let limiter = HostLimiter::for_host(&config.base_url, config.limits);
 
-        Ok(Self { client, config })
+        Ok(Self {
+            client,
+            config,
+            limiter,
+            refreshed_auth: Arc::default(),
+        })
     }
 
     fn url(&self, path: &str) -> Result<Url, ApiError> {
-        self.config.base_url.join(path).map_err(ApiError::from)
+        let Some(version) = &self.config.api_version else {
+            return self.config.base_url.join(path).map_err(ApiError::from);
+        };
+        let version = version.trim_matches('/');
+        let path = match path.strip_prefix('/') {
+            Some(rest) => format!("/{version}/{rest}"),
+            None => format!("{version}/{path}"),
+        };
+        self.config.base_url.join(&path).map_err(ApiError::from)
+    }
+
+    fn conditional(&self, url: &Url, builder: RequestBuilder) -> RequestBuilder {
+        match &self.config.cache {
+            Some(cache) => cache.apply_validators(url, builder),
+            None => builder,
+        }
     }
 
     fn apply_auth(&self, builder: RequestBuilder, auth: Option<&AuthContext>) -> RequestBuilder {
-        if let Some(auth_ctx) = auth {
-            auth_ctx.apply(builder)
-        } else {
-            builder
+        match auth {
+            Some(auth_ctx) => auth_ctx.apply(builder),
+            None => builder,
+        }
+    }
+
+    /// The auth to send: the caller's, unless a rejection has already replaced that same auth.
+    fn effective_auth(&self, auth: Option<&AuthContext>) -> Option<AuthContext> {
+        let auth = auth?;
+        let refreshed = self
+            .refreshed_auth
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        match &*refreshed {
+            Some((replaced, fresh)) if replaced == auth => Some(fresh.clone()),
+            _ => Some(auth.clone()),
         }
     }
 
@@ -89,24 +351,113 @@ impl HttpClient {
     {
         let mut url = self.url(path)?;
         pagination.apply(&mut url);
-        let builder = self.apply_auth(self.client.get(url), auth);
-        let response = builder.send().await.map_err(ApiError::Request)?;
-        Self::hydrate_response(response).await
+        let (response, attempts, record) = self
+            .send(Method::GET, auth, |auth| {
+                self.conditional(&url, self.apply_auth(self.client.get(url.clone()), auth))
+            })
+            .await?;
+        self.hydrate_response(&Method::GET, &url, true, response)
+            .await
+            .map_err(|err| err.with_request(record).after_attempts(attempts))
+    }
+
+    /// Streams every page of `path`, following `next` cursors until the server stops sending one.
+    pub fn paginate<'a, T>(
+        &'a self,
+        path: &'a str,
+        auth: Option<&'a AuthContext>,
+    ) -> impl Stream<Item = Result<Vec<T>, ApiError>> + 'a
+    where
+        T: DeserializeOwned + 'a,
+    {
+        let first = Pagination {
+            limit: self.config.page_limits.page_size,
+            cursor: None,
+        };
+
+        stream::try_unfold(
+            (Some(first), HashSet::new()),
+            move |(pagination, mut seen)| async move {
+                let Some(pagination) = pagination else {
+                    return Ok(None);
+                };
+                let page = self.get_paginated::<T>(path, &pagination, auth).await?.data;
+                // A server that hands out a cursor it already sent, directly or after other
+                // pages, would otherwise loop forever.
+                let next = page
+                    .next
+                    .filter(|cursor| !cursor.is_empty() && seen.insert(cursor.clone()))
+                    .map(|cursor| Pagination {
+                        limit: pagination.limit,
+                        cursor: Some(cursor),
+                    });
+                Ok(Some((page.items, (next, seen))))
+            },
+        )
+    }
+
+    /// Collects every page of `path` into one vector, bounded by the configured item cap.
+    pub async fn get_all<T>(
+        &self,
+        path: &str,
+        auth: Option<&AuthContext>,
+    ) -> Result<Vec<T>, ApiError>
+    where
+        T: DeserializeOwned,
+    {
+        let limit = self.config.page_limits.max_items;
+        let pages = self.paginate::<T>(path, auth);
+        futures_util::pin_mut!(pages);
+
+        let mut items = Vec::new();
+        while let Some(page) = pages.try_next().await? {
+            items.extend(page);
+            if items.len() > limit {
+                return Err(ApiError::TooManyItems {
+                    path: path.to_string(),
+                    limit,
+                });
+            }
+        }
+        Ok(items)
+    }
+
+    /// Sends an unauthenticated GET to the base URL and returns whichever status came back.
+    ///
+    /// Any status, including 4xx/5xx, proves DNS, TCP and TLS all succeeded.
+    pub async fn ping(&self) -> Result<StatusCode, ApiError> {
+        let request = self
+            .client
+            .get(self.config.base_url.clone())
+            .build()
+            .map_err(ApiError::Request)?;
+        let record = RequestRecord::capture(&request);
+        let response = self
+            .execute(request, &record)
+            .await?
+            .map_err(ApiError::Request)?;
+        Ok(response.status())
     }
 
     pub async fn delete(&self, path: &str, auth: Option<&AuthContext>) -> Result<(), ApiError> {
         let url = self.url(path)?;
-        let builder = self.apply_auth(self.client.request(Method::DELETE, url), auth);
-        let response = builder.send().await.map_err(ApiError::Request)?;
+        let (response, attempts, record) = self
+            .send(Method::DELETE, auth, |auth| {
+                self.apply_auth(self.client.request(Method::DELETE, url.clone()), auth)
+            })
+            .await?;
         let status = response.status();
         if status.is_success() {
             Ok(())
         } else {
+            let headers = response.headers().clone();
             let body = response
                 .text()
                 .await
                 .unwrap_or_else(|_| "<failed to read body>".into());
-            Err(ApiError::HttpStatus { status, body })
+            Err(ApiError::from_status(status, &headers, body)
+                .with_request(record)
+                .after_attempts(attempts))
         }
     }
 
@@ -124,6 +475,27 @@ impl HttpClient {
             .await
     }
 
+    /// Posts `form` as `application/x-www-form-urlencoded`, as OAuth2 token endpoints expect.
+    pub async fn post_form<T>(
+        &self,
+        path: &str,
+        form: &[(&str, &str)],
+        auth: Option<&AuthContext>,
+    ) -> Result<ApiResponse<T>, ApiError>
+    where
+        T: DeserializeOwned,
+    {
+        let url = self.url(path)?;
+        let (response, attempts, record) = self
+            .send(Method::POST, auth, |auth| {
+                self.apply_auth(self.client.post(url.clone()).form(form), auth)
+            })
+            .await?;
+        self.hydrate_response(&Method::POST, &url, false, response)
+            .await
+            .map_err(|err| err.with_request(record).after_attempts(attempts))
+    }
+
     pub async fn request_json<T, B>(
         &self,
         method: Method,
@@ -136,30 +508,216 @@ impl HttpClient {
         B: Serialize + ?Sized,
     {
         let url = self.url(path)?;
-        let mut builder = self.client.request(method, url);
-        if let Some(payload) = body {
-            builder = builder.json(payload);
+        let cacheable = method == Method::GET && body.is_none();
+        let (response, attempts, record) = self
+            .send(method.clone(), auth, |auth| {
+                let mut builder = self.client.request(method.clone(), url.clone());
+                if let Some(payload) = body {
+                    builder = builder.json(payload);
+                }
+                let builder = self.apply_auth(builder, auth);
+                if cacheable {
+                    self.conditional(&url, builder)
+                } else {
+                    builder
+                }
+            })
+            .await?;
+        self.hydrate_response(&method, &url, cacheable, response)
+            .await
+            .map_err(|err| err.with_request(record).after_attempts(attempts))
+    }
+
+    /// Sends the request built by `build`, retrying according to the configured policy.
+    ///
+    /// Fails immediately while the circuit breaker is open. Each attempt first waits for the
+    /// host's in-flight and rate limits, holding its slot until the response headers arrive.
+    /// An authenticated request rejected with `401` or `419` is sent once more with fresh auth
+    /// when a [`Reauthenticate`] hook is configured. Returns the final response, whatever its
+    /// status, the number of attempts made and a redacted record of the last request sent.
+    async fn send(
+        &self,
+        method: Method,
+        auth: Option<&AuthContext>,
+        build: impl Fn(Option<&AuthContext>) -> RequestBuilder,
+    ) -> Result<(Response, u32, RequestRecord), ApiError> {
+        if let Some(breaker) = &self.config.breaker {
+            breaker
+                .admit()
+                .map_err(|retry_in| ApiError::CircuitOpen { retry_in })?;
+        }
+        let sent = self.effective_auth(auth);
+        let mut result = self
+            .send_with_retries(method.clone(), || build(sent.as_ref()))
+            .await;
+        if let (Ok((response, ..)), Some(reauth), Some(auth), Some(rejected)) =
+            (&result, &self.config.reauth, auth, &sent)
+        {
+            let status = response.status();
+            if is_auth_rejection(status) {
+                if let Some(fresh) = reauth.reauthenticate(status, rejected).await {
+                    *self
+                        .refreshed_auth
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner()) =
+                        Some((auth.clone(), fresh.clone()));
+                    result = self.send_with_retries(method, || build(Some(&fresh))).await;
+                }
+            }
+        }
+        if let Some(breaker) = &self.config.breaker {
+            match &result {
+                Ok((response, ..)) => {
+                    let status = response.status();
+                    if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+                        breaker.record_failure()
+                    } else {
+                        breaker.record_success()
+                    }
+                }
+                Err(err) if err.is_transport() => breaker.record_failure(),
+                // Configuration, credential and cassette errors say nothing about the service.
+                Err(_) => {}
+            }
+        }
+        result
+    }
+
+    async fn send_with_retries(
+        &self,
+        method: Method,
+        build: impl Fn() -> RequestBuilder,
+    ) -> Result<(Response, u32, RequestRecord), ApiError> {
+        let policy = &self.config.retry;
+        let mut attempt = 1;
+
+        loop {
+            let request = build().build().map_err(ApiError::Request)?;
+            let record = RequestRecord::capture(&request);
+            let permit = match &self.limiter {
+                Some(limiter) => Some(limiter.acquire().await),
+                None => None,
+            };
+            let queued = permit
+                .as_ref()
+                .map(|permit| permit.queued)
+                .unwrap_or_default();
+            let started = Instant::now();
+            if let Some(cache) = &self.config.cache {
+                cache.record_request();
+            }
+            let outcome = match self.execute(request, &record).await {
+                Ok(outcome) => outcome,
+                Err(err) => return Err(err.with_request(record).after_attempts(attempt)),
+            };
+            drop(permit);
+            let latency = started.elapsed();
+            trace::log_attempt(&record, &outcome, latency, queued, attempt);
+            if let Some(metrics) = &self.config.metrics {
+                let status = outcome.as_ref().ok().map(Response::status);
+                metrics.record_attempt(&record.method, &record.url, status, latency);
+            }
+            let retry = match &outcome {
+                Ok(response) if policy.retries_status(response.status()) => {
+                    Some(retry_after(response.headers()))
+                }
+                Err(err) if is_transient(err) => Some(None),
+                _ => None,
+            };
+
+            match retry {
+                Some(retry_after)
+                    if policy.retries_method(&method) && attempt < policy.max_attempts =>
+                {
+                    time::sleep(policy.delay(attempt, retry_after)).await;
+                    attempt += 1;
+                }
+                _ => {
+                    return match outcome {
+                        Ok(response) => Ok((response, attempt, record)),
+                        Err(err) => Err(ApiError::Request(err)
+                            .with_request(record)
+                            .after_attempts(attempt)),
+                    };
+                }
+            }
         }
-        builder = self.apply_auth(builder, auth);
+    }
 
-        let response = builder.send().await.map_err(ApiError::Request)?;
-        Self::hydrate_response(response).await
+    /// Sends `request` over the network, recording the exchange when a cassette is recording,
+    /// or answers it from the cassette in replay mode without any network access.
+    async fn execute(
+        &self,
+        request: Request,
+        record: &RequestRecord,
+    ) -> Result<Result<Response, reqwest::Error>, ApiError> {
+        match &self.config.cassette {
+            Some(cassette) if cassette.is_replay() => cassette.respond(record).map(Ok),
+            Some(cassette) => match self.client.execute(request).await {
+                Ok(response) => cassette.capture(record, response).await.map(Ok),
+                Err(err) => Ok(Err(err)),
+            },
+            None => Ok(self.client.execute(request).await),
+        }
     }
 
-    async fn hydrate_response<T>(response: reqwest::Response) -> Result<ApiResponse<T>, ApiError>
+    /// Decodes `response`; cacheable GETs are answered from the cache on `304 Not Modified`.
+    async fn hydrate_response<T>(
+        &self,
+        method: &Method,
+        url: &Url,
+        cacheable: bool,
+        response: reqwest::Response,
+    ) -> Result<ApiResponse<T>, ApiError>
     where
         T: DeserializeOwned,
     {
         let status = response.status();
         let headers = response.headers().clone();
+        let cache = self.config.cache.as_deref().filter(|_| cacheable);
+
+        if status == StatusCode::NOT_MODIFIED {
+            if let Some(value) = cache.and_then(|cache| cache.revalidated(url)) {
+                let data = T::deserialize(&*value).map_err(|source| ApiError::Deserialize {
+                    source,
+                    body: value.to_string(),
+                })?;
+                return Ok(ApiResponse {
+                    data,
+                    status,
+                    headers,
+                });
+            }
+        }
+
         let body = response.text().await.map_err(ApiError::Request)?;
+        trace::log_response_body(&body);
+        if let Some(cache) = &self.config.cache {
+            cache.record_bytes(body.len());
+        }
+        if let Some(metrics) = &self.config.metrics {
+            metrics.record_bytes(method, url, body.len());
+        }
 
         if !status.is_success() {
-            return Err(ApiError::HttpStatus { status, body });
+            return Err(ApiError::from_status(status, &headers, body));
         }
 
-        let data =
-            serde_json::from_str(&body).map_err(|source| ApiError::Deserialize { source, body })?;
+        let data = match cache {
+            Some(cache) => {
+                let decoded = serde_json::from_str::<serde_json::Value>(&body)
+                    .and_then(|value| Ok((T::deserialize(&value)?, value)));
+                match decoded {
+                    Ok((data, value)) => {
+                        cache.store(url, &headers, Arc::new(value), body.len());
+                        data
+                    }
+                    Err(source) => return Err(ApiError::Deserialize { source, body }),
+                }
+            }
+            None => serde_json::from_str(&body)
+                .map_err(|source| ApiError::Deserialize { source, body })?,
+        };
 
         Ok(ApiResponse {
             data,
@@ -169,10 +727,12 @@ impl HttpClient {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct AuthContext {
     pub bearer_token: Option<String>,
     pub basic: Option<(String, String)>,
+    /// Redfish session token, sent as `X-Auth-Token`.
+    pub session_token: Option<String>,
 }
 
 impl AuthContext {
@@ -190,7 +750,17 @@ impl AuthContext {
         }
     }
 
+    pub fn session(token: impl Into<String>) -> Self {
+        Self {
+            session_token: Some(token.into()),
+            ..Default::default()
+        }
+    }
+
     pub fn apply(&self, mut builder: RequestBuilder) -> RequestBuilder {
+        if let Some(token) = &self.session_token {
+            builder = builder.header("X-Auth-Token", token);
+        }
         if let Some(token) = &self.bearer_token {
             builder = builder.bearer_auth(token);
         }
@@ -251,8 +821,13 @@ pub enum ApiError {
     Url(#[from] url::ParseError),
     #[error("request error: {0}")]
     Request(#[from] reqwest::Error),
-    #[error("HTTP {status}: {body}")]
-    HttpStatus { status: StatusCode, body: String },
+    #[error("HTTP {status}: {}", status_message(.body, .details.as_deref()))]
+    HttpStatus {
+        status: StatusCode,
+        body: String,
+        /// Structured fields decoded from `body`, when it follows a known error format.
+        details: Option<Box<ErrorDetails>>,
+    },
     #[error("failed to deserialize response: {source}")]
     Deserialize {
         source: serde_json::Error,
@@ -260,4 +835,564 @@ pub enum ApiError {
     },
     #[error("missing expected authentication token in response headers")]
     MissingAuthToken,
+    #[error(transparent)]
+    Credentials(#[from] AuthError),
+    #[error("OAuth2 configuration error: {0}")]
+    OAuth(String),
+    #[error("{path} returned more than {limit} items; refusing to follow further pages")]
+    TooManyItems { path: String, limit: usize },
+    #[error("TLS configuration error: {0}")]
+    Tls(String),
+    #[error("proxy configuration error: {0}")]
+    Proxy(String),
+    #[error("request limit configuration error: {0}")]
+    Limits(String),
+    #[error("invalid header configuration: {0}")]
+    Header(String),
+    #[error("cassette error: {0}")]
+    Cassette(String),
+    #[error("no recorded response for {0}")]
+    NotRecorded(String),
+    #[error(
+        "service unavailable after repeated failures; next attempt in {}s",
+        retry_in.as_secs().max(1)
+    )]
+    CircuitOpen { retry_in: Duration },
+    #[error("{last} (after {attempts} attempts)")]
+    Retried { attempts: u32, last: Box<ApiError> },
+    /// Attaches the redacted request that produced `error`; displays as `error` alone.
+    #[error("{error}")]
+    WithRequest {
+        request: Box<RequestRecord>,
+        error: Box<ApiError>,
+    },
+}
+
+impl ApiError {
+    fn from_status(status: StatusCode, headers: &HeaderMap, body: String) -> Self {
+        let content_type = headers
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok());
+        let details = ErrorDetails::parse(content_type, &body).map(Box::new);
+        ApiError::HttpStatus {
+            status,
+            body,
+            details,
+        }
+    }
+
+    /// Structured error fields from the final attempt's response body, if any were decoded.
+    pub fn details(&self) -> Option<&ErrorDetails> {
+        match self.last_attempt() {
+            ApiError::HttpStatus { details, .. } => details.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Records how many attempts led to this error; single attempts are left unwrapped.
+    fn after_attempts(self, attempts: u32) -> Self {
+        if attempts > 1 {
+            ApiError::Retried {
+                attempts,
+                last: Box::new(self),
+            }
+        } else {
+            self
+        }
+    }
+
+    fn with_request(self, request: RequestRecord) -> Self {
+        ApiError::WithRequest {
+            request: Box::new(request),
+            error: Box::new(self),
+        }
+    }
+
+    /// The error from the final attempt, looking through retry and request wrappers.
+    pub fn last_attempt(&self) -> &ApiError {
+        match self {
+            ApiError::Retried { last, .. } => last.last_attempt(),
+            ApiError::WithRequest { error, .. } => error.last_attempt(),
+            other => other,
+        }
+    }
+
+    /// Whether the final attempt failed to reach the service: connection, TLS or timeout errors,
+    /// as opposed to a request that could not be built.
+    pub(crate) fn is_transport(&self) -> bool {
+        matches!(self.last_attempt(), ApiError::Request(err) if !err.is_builder())
+    }
+
+    /// Whether the final attempt was rejected with `401 Unauthorized` (or `419`).
+    pub fn is_unauthorized(&self) -> bool {
+        matches!(
+            self.last_attempt(),
+            ApiError::HttpStatus { status, .. } if is_auth_rejection(*status)
+        )
+    }
+
+    /// The redacted request behind this error, for "copy as curl".
+    pub fn request(&self) -> Option<&RequestRecord> {
+        match self {
+            ApiError::Retried { last, .. } => last.request(),
+            ApiError::WithRequest { request, .. } => Some(request),
+            _ => None,
+        }
+    }
+}
+
+/// Prefers the decoded summary over the raw body, which may be a large JSON document.
+fn status_message(body: &str, details: Option<&ErrorDetails>) -> String {
+    details
+        .and_then(ErrorDetails::summary)
+        .unwrap_or_else(|| body.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::breaker::{BreakerPolicy, BreakerStatus};
+    use super::super::cache::TransferStats;
+    use super::super::metrics::RequestMetrics;
+    use super::*;
+    use httpmock::prelude::*;
+
+    fn fast_retries() -> RetryPolicy {
+        RetryPolicy {
+            base_delay: Duration::from_millis(1),
+            jitter: 0.0,
+            ..RetryPolicy::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_idempotent_requests_and_reports_attempts() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/items");
+            then.status(503).header("Retry-After", "0");
+        });
+
+        let config = ApiClientConfig::try_from_url(&server.url("/"))
+            .unwrap()
+            .with_retry_policy(fast_retries());
+        let client = HttpClient::new(config).unwrap();
+        let err = client
+            .get_json::<serde_json::Value>("/items", None)
+            .await
+            .unwrap_err();
+
+        mock.assert_hits(3);
+        assert!(matches!(err, ApiError::Retried { attempts: 3, .. }));
+        assert!(matches!(
+            err.last_attempt(),
+            ApiError::HttpStatus { status, .. } if *status == StatusCode::SERVICE_UNAVAILABLE
+        ));
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_post_by_default() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/items");
+            then.status(503);
+        });
+
+        let config = ApiClientConfig::try_from_url(&server.url("/"))
+            .unwrap()
+            .with_retry_policy(fast_retries());
+        let client = HttpClient::new(config).unwrap();
+        let err = client
+            .post_json::<serde_json::Value, _>("/items", &serde_json::json!({}), None)
+            .await
+            .unwrap_err();
+
+        mock.assert_hits(1);
+        assert!(matches!(err.last_attempt(), ApiError::HttpStatus { .. }));
+        let request = err.request().expect("failed request is recorded");
+        assert_eq!(request.method, Method::POST);
+        assert!(request.to_curl().starts_with("curl -X POST"));
+    }
+
+    #[test]
+    fn honours_retry_after_up_to_max_delay() {
+        let policy = fast_retries();
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, "7".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(7)));
+        assert_eq!(
+            policy.delay(1, Some(Duration::from_secs(7))),
+            Duration::from_secs(7)
+        );
+        assert_eq!(
+            policy.delay(1, Some(Duration::from_secs(600))),
+            policy.max_delay
+        );
+        assert_eq!(policy.delay(3, None), Duration::from_millis(4));
+    }
+
+    #[tokio::test]
+    async fn follows_next_cursors_across_pages() {
+        let server = MockServer::start();
+        let first = server.mock(|when, then| {
+            when.method(GET)
+                .path("/items")
+                .query_param("limit", "2")
+                .matches(|req| {
+                    !req.query_params
+                        .iter()
+                        .flatten()
+                        .any(|(name, _)| name == "cursor")
+                });
+            then.status(200)
+                .json_body(serde_json::json!({ "items": [1, 2], "next": "page-2" }));
+        });
+        let second = server.mock(|when, then| {
+            when.method(GET)
+                .path("/items")
+                .query_param("cursor", "page-2");
+            then.status(200)
+                .json_body(serde_json::json!({ "items": [3], "next": null }));
+        });
+
+        let config = ApiClientConfig::try_from_url(&server.url("/"))
+            .unwrap()
+            .with_page_limits(PageLimits {
+                page_size: Some(2),
+                max_items: 10,
+            });
+        let client = HttpClient::new(config).unwrap();
+        let items: Vec<u32> = client.get_all("/items", None).await.unwrap();
+
+        first.assert();
+        second.assert();
+        assert_eq!(items, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn stops_when_cursors_cycle() {
+        let server = MockServer::start();
+        let first = server.mock(|when, then| {
+            when.method(GET).path("/items").matches(|req| {
+                !req.query_params
+                    .iter()
+                    .flatten()
+                    .any(|(name, _)| name == "cursor")
+            });
+            then.status(200)
+                .json_body(serde_json::json!({ "items": [1], "next": "a" }));
+        });
+        let a = server.mock(|when, then| {
+            when.method(GET).path("/items").query_param("cursor", "a");
+            then.status(200)
+                .json_body(serde_json::json!({ "items": [], "next": "b" }));
+        });
+        let b = server.mock(|when, then| {
+            when.method(GET).path("/items").query_param("cursor", "b");
+            then.status(200)
+                .json_body(serde_json::json!({ "items": [], "next": "a" }));
+        });
+
+        let config = ApiClientConfig::try_from_url(&server.url("/")).unwrap();
+        let client = HttpClient::new(config).unwrap();
+        let items: Vec<u32> = client.get_all("/items", None).await.unwrap();
+
+        assert_eq!(items, vec![1]);
+        first.assert();
+        a.assert();
+        b.assert();
+    }
+
+    #[tokio::test]
+    async fn stops_collecting_at_the_item_cap() {
+        let server = MockServer::start();
+        let _mock = server.mock(|when, then| {
+            when.method(GET).path("/items");
+            then.status(200)
+                .json_body(serde_json::json!({ "items": [1, 2, 3] }));
+        });
+
+        let config = ApiClientConfig::try_from_url(&server.url("/"))
+            .unwrap()
+            .with_page_limits(PageLimits {
+                page_size: None,
+                max_items: 2,
+            });
+        let client = HttpClient::new(config).unwrap();
+        let err = client.get_all::<u32>("/items", None).await.unwrap_err();
+        assert!(matches!(err, ApiError::TooManyItems { limit: 2, .. }));
+    }
+
+    #[tokio::test]
+    async fn decodes_problem_json_error_bodies() {
+        let server = MockServer::start();
+        let _mock = server.mock(|when, then| {
+            when.method(GET).path("/fabrics/fab-9");
+            then.status(404)
+                .header("content-type", "application/problem+json")
+                .body(r#"{"title": "Fabric not found", "detail": "No fabric with id fab-9"}"#);
+        });
+
+        let config = ApiClientConfig::try_from_url(&server.url("/")).unwrap();
+        let client = HttpClient::new(config).unwrap();
+        let err = client
+            .get_json::<serde_json::Value>("/fabrics/fab-9", None)
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "HTTP 404 Not Found: Fabric not found: No fabric with id fab-9"
+        );
+        assert_eq!(
+            err.details().and_then(|details| details.title.as_deref()),
+            Some("Fabric not found")
+        );
+    }
+
+    #[tokio::test]
+    async fn serves_not_modified_responses_from_the_cache() {
+        let server = MockServer::start();
+        let mut fresh = server.mock(|when, then| {
+            when.method(GET).path("/fabrics/fab-1");
+            then.status(200)
+                .header("etag", "\"v1\"")
+                .json_body(serde_json::json!({ "id": "fab-1", "name": "alpha" }));
+        });
+
+        let cache = Arc::new(HttpCache::new());
+        let config = ApiClientConfig::try_from_url(&server.url("/"))
+            .unwrap()
+            .with_cache(cache.clone());
+        let client = HttpClient::new(config).unwrap();
+        let first = client
+            .get_json::<serde_json::Value>("/fabrics/fab-1", None)
+            .await
+            .unwrap();
+        fresh.assert();
+        fresh.delete();
+
+        let revalidated = server.mock(|when, then| {
+            when.method(GET)
+                .path("/fabrics/fab-1")
+                .header("if-none-match", "\"v1\"");
+            then.status(304);
+        });
+        let second = client
+            .get_json::<serde_json::Value>("/fabrics/fab-1", None)
+            .await
+            .unwrap();
+        revalidated.assert();
+        assert_eq!(second.status, StatusCode::NOT_MODIFIED);
+        assert_eq!(first.data, second.data);
+
+        let stats = cache.take_stats();
+        assert_eq!(stats.requests, 2);
+        assert_eq!(stats.not_modified, 1);
+        assert!(stats.bytes_received > 0);
+        assert_eq!(stats.bytes_saved, stats.bytes_received);
+        assert_eq!(cache.take_stats(), TransferStats::default());
+    }
+
+    #[derive(Debug, Default)]
+    struct CountingReauth {
+        calls: std::sync::atomic::AtomicU32,
+    }
+
+    impl Reauthenticate for CountingReauth {
+        fn reauthenticate<'a>(
+            &'a self,
+            _status: StatusCode,
+            rejected: &'a AuthContext,
+        ) -> BoxFuture<'a, Option<AuthContext>> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            let fresh = match rejected.bearer_token.as_deref() {
+                Some("stale") => Some(AuthContext::bearer("fresh")),
+                _ => None,
+            };
+            Box::pin(async move { fresh })
+        }
+    }
+
+    #[tokio::test]
+    async fn reauthenticates_once_after_unauthorized() {
+        let server = MockServer::start();
+        let stale = server.mock(|when, then| {
+            when.method(GET)
+                .path("/fabrics")
+                .header("authorization", "Bearer stale");
+            then.status(401);
+        });
+        let fresh = server.mock(|when, then| {
+            when.method(GET)
+                .path("/fabrics")
+                .header("authorization", "Bearer fresh");
+            then.status(200).json_body(serde_json::json!([]));
+        });
+        let expired = server.mock(|when, then| {
+            when.method(GET).path("/workloads");
+            then.status(419);
+        });
+
+        let reauth = Arc::new(CountingReauth::default());
+        let config = ApiClientConfig::try_from_url(&server.url("/"))
+            .unwrap()
+            .with_reauth(reauth.clone());
+        let client = HttpClient::new(config).unwrap();
+        let auth = AuthContext::bearer("stale");
+        for _ in 0..2 {
+            client
+                .get_json::<serde_json::Value>("/fabrics", Some(&auth))
+                .await
+                .unwrap();
+        }
+        // The refreshed token is kept for later requests from the same client.
+        stale.assert_hits(1);
+        fresh.assert_hits(2);
+
+        // Rejected fresh auth is handed to the hook, which has nothing newer to offer.
+        let err = client
+            .get_json::<serde_json::Value>("/workloads", Some(&auth))
+            .await
+            .unwrap_err();
+        assert!(err.is_unauthorized());
+        expired.assert_hits(1);
+        assert_eq!(reauth.calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        // Different auth from the caller is sent as given, not replaced by the refreshed token.
+        let newer = server.mock(|when, then| {
+            when.method(GET)
+                .path("/fabrics")
+                .header("authorization", "Bearer newer");
+            then.status(200).json_body(serde_json::json!([]));
+        });
+        client
+            .get_json::<serde_json::Value>("/fabrics", Some(&AuthContext::bearer("newer")))
+            .await
+            .unwrap();
+        newer.assert_hits(1);
+        fresh.assert_hits(2);
+    }
+
+    #[tokio::test]
+    async fn open_circuit_fails_fast_without_sending() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/workloads");
+            then.status(503);
+        });
+
+        let breaker = Arc::new(CircuitBreaker::new(BreakerPolicy {
+            failure_threshold: 2,
+            cooldown: Duration::from_secs(60),
+        }));
+        let config = ApiClientConfig::try_from_url(&server.url("/"))
+            .unwrap()
+            .with_retry_policy(RetryPolicy {
+                max_attempts: 1,
+                ..RetryPolicy::default()
+            })
+            .with_breaker(breaker.clone());
+        let client = HttpClient::new(config).unwrap();
+
+        for _ in 0..2 {
+            let err = client
+                .get_json::<serde_json::Value>("/workloads", None)
+                .await
+                .unwrap_err();
+            assert!(matches!(err.last_attempt(), ApiError::HttpStatus { .. }));
+        }
+        let err = client
+            .get_json::<serde_json::Value>("/workloads", None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ApiError::CircuitOpen { .. }));
+        assert!(matches!(breaker.status(), BreakerStatus::Open { .. }));
+        mock.assert_hits(2);
+    }
+
+    #[tokio::test]
+    async fn local_errors_leave_the_circuit_closed() {
+        let dir = std::env::temp_dir().join(format!("fabrexlens-breaker-{}", std::process::id()));
+        Cassette::record(&dir).unwrap();
+        let breaker = Arc::new(CircuitBreaker::new(BreakerPolicy {
+            failure_threshold: 1,
+            cooldown: Duration::from_secs(60),
+        }));
+        let config = ApiClientConfig::try_from_url("https://fabrex.example.com/")
+            .unwrap()
+            .with_cassette(Cassette::replay(&dir).unwrap())
+            .with_breaker(breaker.clone());
+        let client = HttpClient::new(config).unwrap();
+
+        let err = client
+            .get_json::<serde_json::Value>("/fabrics", None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err.last_attempt(), ApiError::NotRecorded(_)));
+        assert_eq!(breaker.status(), BreakerStatus::Closed);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn prefixes_api_version_and_sends_static_headers() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/v2/fabrics")
+                .header("x-tenant", "lab");
+            then.status(200).json_body(serde_json::json!([]));
+        });
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-tenant", "lab".parse().unwrap());
+        let config = ApiClientConfig::try_from_url(&server.url("/"))
+            .unwrap()
+            .with_api_version("v2")
+            .with_headers(headers);
+        let client = HttpClient::new(config).unwrap();
+        client
+            .get_json::<serde_json::Value>("/fabrics", None)
+            .await
+            .unwrap();
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn records_metrics_per_route() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/fabrics/fab-7");
+            then.status(200).body("{}");
+        });
+
+        let metrics = Arc::new(RequestMetrics::new());
+        let config = ApiClientConfig::try_from_url(&server.url("/"))
+            .unwrap()
+            .with_metrics(metrics.for_service("FabreX"));
+        let client = HttpClient::new(config).unwrap();
+        client
+            .get_json::<serde_json::Value>("/fabrics/fab-7", None)
+            .await
+            .unwrap();
+
+        let stats = metrics.snapshot();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].route, "GET /fabrics/{id}");
+        assert_eq!(stats[0].requests, 1);
+        assert_eq!(stats[0].error_rate, 0.0);
+        assert_eq!(stats[0].bytes, 2);
+    }
+
+    #[tokio::test]
+    async fn ping_reports_error_statuses_without_failing() {
+        let server = MockServer::start();
+        let _mock = server.mock(|when, then| {
+            when.method(GET).path("/api/");
+            then.status(401);
+        });
+
+        let config = ApiClientConfig::try_from_url(&server.url("/api/")).unwrap();
+        let client = HttpClient::new(config).unwrap();
+        assert_eq!(client.ping().await.unwrap(), StatusCode::UNAUTHORIZED);
+    }
 }
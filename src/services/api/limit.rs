@@ -0,0 +1,243 @@
+use super::http::ApiError;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time;
+use url::Url;
+
+/// Client-side limits applied to every request sent to one host.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RequestLimits {
+    /// Maximum number of requests awaiting a response at once.
+    pub max_in_flight: Option<usize>,
+    /// Sustained request rate enforced with a token bucket.
+    pub requests_per_second: Option<f64>,
+    /// Requests that may be sent back to back before the rate applies; defaults to one
+    /// second's worth of requests.
+    pub burst: Option<u32>,
+}
+
+impl RequestLimits {
+    pub fn is_unlimited(&self) -> bool {
+        self.max_in_flight.is_none() && self.requests_per_second.is_none()
+    }
+
+    pub fn validate(&self) -> Result<(), ApiError> {
+        if self.max_in_flight == Some(0) {
+            return Err(ApiError::Limits("max_in_flight must be at least 1".into()));
+        }
+        if let Some(rate) = self.requests_per_second {
+            if !(rate.is_finite() && rate > 0.0) {
+                return Err(ApiError::Limits(format!(
+                    "requests_per_second must be positive, got {rate}"
+                )));
+            }
+        }
+        if self.burst == Some(0) {
+            return Err(ApiError::Limits("burst must be at least 1".into()));
+        }
+        if self.burst.is_some() && self.requests_per_second.is_none() {
+            return Err(ApiError::Limits(
+                "burst requires requests_per_second".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Concurrency and rate state shared by every client talking to the same host with the same
+/// limits, so rebuilding clients on each refresh does not reset them.
+///
+/// One limiter exists per `host:port` and set of limits, so services that share a host but are
+/// configured differently each keep the limits they asked for.
+#[derive(Debug)]
+pub(crate) struct HostLimiter {
+    limits: RequestLimits,
+    in_flight: Option<Arc<Semaphore>>,
+    bucket: Option<Mutex<TokenBucket>>,
+}
+
+/// Held while a request is outstanding; dropping it frees the in-flight slot.
+#[derive(Debug)]
+pub(crate) struct Permit {
+    _slot: Option<OwnedSemaphorePermit>,
+    /// Time spent waiting for a slot and a rate token.
+    pub(crate) queued: Duration,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    rate: f64,
+    capacity: f64,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+type Registry = Mutex<HashMap<String, Vec<Arc<HostLimiter>>>>;
+
+impl HostLimiter {
+    /// The shared limiter for the host of `url` and `limits`, or `None` when `limits` impose
+    /// nothing.
+    pub(crate) fn for_host(url: &Url, limits: RequestLimits) -> Option<Arc<Self>> {
+        static REGISTRY: OnceLock<Registry> = OnceLock::new();
+
+        if limits.is_unlimited() {
+            return None;
+        }
+        let key = format!(
+            "{}:{}",
+            url.host_str().unwrap_or_default(),
+            url.port_or_known_default().unwrap_or_default()
+        );
+        let mut registry = REGISTRY
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let limiters = registry.entry(key).or_default();
+        if let Some(limiter) = limiters.iter().find(|limiter| limiter.limits == limits) {
+            return Some(limiter.clone());
+        }
+        let limiter = Arc::new(Self::new(limits));
+        limiters.push(limiter.clone());
+        Some(limiter)
+    }
+
+    fn new(limits: RequestLimits) -> Self {
+        let bucket = limits.requests_per_second.map(|rate| {
+            let capacity = limits
+                .burst
+                .map(f64::from)
+                .unwrap_or_else(|| rate.ceil())
+                .max(1.0);
+            Mutex::new(TokenBucket {
+                rate,
+                capacity,
+                tokens: capacity,
+                refilled_at: Instant::now(),
+            })
+        });
+        Self {
+            limits,
+            in_flight: limits
+                .max_in_flight
+                .map(|max| Arc::new(Semaphore::new(max))),
+            bucket,
+        }
+    }
+
+    /// Waits for an in-flight slot, then for a rate token.
+    pub(crate) async fn acquire(&self) -> Permit {
+        let started = Instant::now();
+        let slot = match &self.in_flight {
+            Some(semaphore) => semaphore.clone().acquire_owned().await.ok(),
+            None => None,
+        };
+        if let Some(bucket) = &self.bucket {
+            loop {
+                let wait = bucket
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .take();
+                match wait {
+                    Some(wait) => time::sleep(wait).await,
+                    None => break,
+                }
+            }
+        }
+        Permit {
+            _slot: slot,
+            queued: started.elapsed(),
+        }
+    }
+}
+
+impl TokenBucket {
+    /// Takes a token, or returns how long until one is available.
+    fn take(&mut self) -> Option<Duration> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.refilled_at = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            None
+        } else {
+            Some(Duration::from_secs_f64((1.0 - self.tokens) / self.rate))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn bounds_in_flight_requests_per_host() {
+        let url = Url::parse("https://bmc-limit-test.example.com/redfish/v1/").unwrap();
+        let limits = RequestLimits {
+            max_in_flight: Some(1),
+            ..RequestLimits::default()
+        };
+        let limiter = HostLimiter::for_host(&url, limits).unwrap();
+        let same_host = Url::parse("https://bmc-limit-test.example.com/other").unwrap();
+        assert!(Arc::ptr_eq(
+            &limiter,
+            &HostLimiter::for_host(&same_host, limits).unwrap()
+        ));
+        // A client configured differently for the host keeps its own limits.
+        let looser = RequestLimits {
+            max_in_flight: Some(4),
+            ..RequestLimits::default()
+        };
+        let separate = HostLimiter::for_host(&same_host, looser).unwrap();
+        assert!(!Arc::ptr_eq(&limiter, &separate));
+        assert_eq!(separate.limits, looser);
+        assert!(HostLimiter::for_host(&same_host, RequestLimits::default()).is_none());
+
+        let first = limiter.acquire().await;
+        let waiting = tokio::spawn({
+            let limiter = limiter.clone();
+            async move { limiter.acquire().await.queued }
+        });
+        time::sleep(Duration::from_millis(50)).await;
+        drop(first);
+        assert!(waiting.await.unwrap() >= Duration::from_millis(40));
+    }
+
+    #[tokio::test]
+    async fn spaces_requests_beyond_the_burst() {
+        let limiter = HostLimiter::new(RequestLimits {
+            requests_per_second: Some(20.0),
+            burst: Some(2),
+            ..RequestLimits::default()
+        });
+
+        let started = Instant::now();
+        for _ in 0..4 {
+            limiter.acquire().await;
+        }
+        // Two requests use the burst; the other two wait 50 ms each.
+        assert!(started.elapsed() >= Duration::from_millis(90));
+    }
+
+    #[test]
+    fn rejects_invalid_limits() {
+        let zero = RequestLimits {
+            max_in_flight: Some(0),
+            ..RequestLimits::default()
+        };
+        assert!(zero.validate().is_err());
+        let burst_only = RequestLimits {
+            burst: Some(5),
+            ..RequestLimits::default()
+        };
+        assert!(burst_only.validate().is_err());
+        assert!(RequestLimits::default().validate().is_ok());
+        assert!(HostLimiter::for_host(
+            &Url::parse("https://example.com").unwrap(),
+            RequestLimits::default()
+        )
+        .is_none());
+    }
+}
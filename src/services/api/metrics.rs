@@ -0,0 +1,223 @@
+use reqwest::{Method, StatusCode};
+use serde::Serialize;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use url::Url;
+
+/// Latency samples kept per route; percentiles and the error rate cover this window.
+const WINDOW: usize = 500;
+/// Routes tracked per service before further ones are folded into [`OTHER_ROUTE`].
+const MAX_ROUTES: usize = 200;
+const OTHER_ROUTE: &str = "(other)";
+
+/// Per-route latency, status and byte counts for every request a session sends.
+///
+/// Shared by every client of a session, like [`super::HttpCache`]; each client records under its
+/// own service name through a [`ServiceMetrics`] handle.
+#[derive(Debug, Default)]
+pub struct RequestMetrics {
+    routes: Mutex<BTreeMap<(String, String), RouteSamples>>,
+}
+
+/// Records into a [`RequestMetrics`] under one service name.
+#[derive(Debug, Clone)]
+pub struct ServiceMetrics {
+    metrics: Arc<RequestMetrics>,
+    service: String,
+}
+
+#[derive(Debug, Default)]
+struct RouteSamples {
+    requests: u64,
+    bytes: u64,
+    /// Latency and whether the attempt failed, most recent last.
+    recent: VecDeque<(Duration, bool)>,
+}
+
+/// Aggregated numbers for one route, as shown in the "Backend performance" panel.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteStats {
+    pub service: String,
+    /// Method and path template, e.g. `GET /fabrics/{id}/usage`.
+    pub route: String,
+    pub requests: u64,
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+    /// Share of recent attempts that failed to connect or returned a 4xx/5xx status.
+    pub error_rate: f64,
+    /// Response body bytes received.
+    pub bytes: u64,
+}
+
+impl RequestMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn for_service(self: &Arc<Self>, service: impl Into<String>) -> ServiceMetrics {
+        ServiceMetrics {
+            metrics: self.clone(),
+            service: service.into(),
+        }
+    }
+
+    /// Current numbers for every route, ordered by service and route.
+    pub fn snapshot(&self) -> Vec<RouteStats> {
+        let routes = self.lock();
+        routes
+            .iter()
+            .map(|((service, route), samples)| {
+                let mut latencies: Vec<Duration> =
+                    samples.recent.iter().map(|(latency, _)| *latency).collect();
+                latencies.sort();
+                let errors = samples.recent.iter().filter(|(_, failed)| *failed).count();
+                RouteStats {
+                    service: service.clone(),
+                    route: route.clone(),
+                    requests: samples.requests,
+                    p50_ms: percentile(&latencies, 0.50),
+                    p95_ms: percentile(&latencies, 0.95),
+                    error_rate: if samples.recent.is_empty() {
+                        0.0
+                    } else {
+                        errors as f64 / samples.recent.len() as f64
+                    },
+                    bytes: samples.bytes,
+                }
+            })
+            .collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<(String, String), RouteSamples>> {
+        self.routes
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn with_route(&self, service: &str, route: String, update: impl FnOnce(&mut RouteSamples)) {
+        let mut routes = self.lock();
+        let key = (service.to_string(), route);
+        let key = if routes.contains_key(&key)
+            || routes.keys().filter(|(name, _)| name == service).count() < MAX_ROUTES
+        {
+            key
+        } else {
+            (service.to_string(), OTHER_ROUTE.to_string())
+        };
+        update(routes.entry(key).or_default());
+    }
+}
+
+impl ServiceMetrics {
+    /// Records one attempt; `status` is `None` when no response arrived.
+    pub(crate) fn record_attempt(
+        &self,
+        method: &Method,
+        url: &Url,
+        status: Option<StatusCode>,
+        latency: Duration,
+    ) {
+        let failed =
+            status.is_none_or(|status| status.is_client_error() || status.is_server_error());
+        self.metrics
+            .with_route(&self.service, route(method, url), |samples| {
+                samples.requests += 1;
+                if samples.recent.len() == WINDOW {
+                    samples.recent.pop_front();
+                }
+                samples.recent.push_back((latency, failed));
+            });
+    }
+
+    pub(crate) fn record_bytes(&self, method: &Method, url: &Url, bytes: usize) {
+        self.metrics
+            .with_route(&self.service, route(method, url), |samples| {
+                samples.bytes += bytes as u64;
+            });
+    }
+}
+
+/// `METHOD /path` with identifier segments replaced by `{id}`.
+///
+/// A segment counts as an identifier when it contains a digit, other than version segments such
+/// as `v1`, so `/fabrics/fab-1/usage` becomes `/fabrics/{id}/usage`.
+fn route(method: &Method, url: &Url) -> String {
+    let path: Vec<&str> = url
+        .path()
+        .split('/')
+        .map(|segment| {
+            let is_version = segment
+                .strip_prefix('v')
+                .is_some_and(|rest| !rest.is_empty() && rest.chars().all(|c| c.is_ascii_digit()));
+            if segment.chars().any(|c| c.is_ascii_digit()) && !is_version {
+                "{id}"
+            } else {
+                segment
+            }
+        })
+        .collect();
+    format!("{method} {}", path.join("/"))
+}
+
+fn percentile(sorted: &[Duration], quantile: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let index = ((sorted.len() as f64 * quantile).ceil() as usize).clamp(1, sorted.len()) - 1;
+    sorted[index].as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_requests_by_route_template() {
+        let url = |path: &str| Url::parse(&format!("https://api.example.com{path}")).unwrap();
+        assert_eq!(
+            route(&Method::GET, &url("/v1/fabrics/fab-1/usage")),
+            "GET /v1/fabrics/{id}/usage"
+        );
+        assert_eq!(
+            route(
+                &Method::POST,
+                &url("/fabrics/fab-1/endpoints/ep-9/reassign")
+            ),
+            "POST /fabrics/{id}/endpoints/{id}/reassign"
+        );
+        assert_eq!(
+            route(&Method::GET, &url("/redfish/v1/SessionService")),
+            "GET /redfish/v1/SessionService"
+        );
+    }
+
+    #[test]
+    fn reports_percentiles_and_error_rate_per_route() {
+        let metrics = Arc::new(RequestMetrics::new());
+        let fabrex = metrics.for_service("FabreX");
+        for (index, fabric) in ["fab-1", "fab-2", "fab-3", "fab-4"].iter().enumerate() {
+            let url =
+                Url::parse(&format!("https://api.example.com/fabrics/{fabric}/usage")).unwrap();
+            let status = if index == 3 {
+                StatusCode::BAD_GATEWAY
+            } else {
+                StatusCode::OK
+            };
+            let latency = Duration::from_millis(100 * (index as u64 + 1));
+            fabrex.record_attempt(&Method::GET, &url, Some(status), latency);
+            fabrex.record_bytes(&Method::GET, &url, 10);
+        }
+
+        let stats = metrics.snapshot();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].service, "FabreX");
+        assert_eq!(stats[0].route, "GET /fabrics/{id}/usage");
+        assert_eq!(stats[0].requests, 4);
+        assert_eq!(stats[0].p50_ms, 200);
+        assert_eq!(stats[0].p95_ms, 400);
+        assert_eq!(stats[0].error_rate, 0.25);
+        assert_eq!(stats[0].bytes, 40);
+    }
+}
@@ -1,12 +1,33 @@
+pub mod breaker;
+pub mod cache;
+pub mod cassette;
 pub mod fabrex;
 pub mod gryf;
 pub mod http;
+pub mod limit;
+pub mod metrics;
+pub mod oauth;
+pub mod problem;
+pub mod proxy;
 pub mod redfish;
 pub mod supernode;
+pub mod tls;
+pub mod trace;
 
+pub use breaker::{BreakerPolicy, BreakerStatus, CircuitBreaker};
+pub use cache::{HttpCache, TransferStats};
+pub use cassette::Cassette;
 pub use fabrex::{
     FabrexClient, FabrexEndpoint, FabrexFabric, FabrexReassignmentResult, FabrexUsage,
 };
 pub use gryf::{GryfClient, GryfWorkload};
-pub use http::{ApiClientConfig, AuthContext};
+pub use http::{ApiClientConfig, AuthContext, HttpClient, Reauthenticate};
+pub use limit::RequestLimits;
+pub use metrics::{RequestMetrics, RouteStats};
+pub use oauth::ClientCredentialsGrant;
+pub use problem::ErrorDetails;
+pub use proxy::{ProxyMode, ProxyOptions};
+pub use redfish::{RedfishClient, RedfishSessionManager};
 pub use supernode::{SupernodeClient, SupernodeNode};
+pub use tls::{ClientIdentity, TlsOptions};
+pub use trace::failed_request_curl;
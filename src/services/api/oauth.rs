@@ -0,0 +1,205 @@
+use super::http::{ApiClientConfig, ApiError, AuthContext, HttpClient};
+use crate::services::auth::{AuthError, CachedToken, CredentialKey, CredentialManager};
+use serde::Deserialize;
+use std::time::Duration;
+use url::Url;
+
+/// Longest head start a token is refreshed with; short-lived tokens refresh after 90% of their
+/// lifetime instead.
+const REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+/// OAuth2 client-credentials grant (RFC 6749 section 4.4) against a token endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientCredentialsGrant {
+    pub token_url: Url,
+    /// Space-separated scopes to request; `None` leaves the scope to the server.
+    pub scope: Option<String>,
+}
+
+/// Successful token endpoint response.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AccessToken {
+    pub access_token: String,
+    #[serde(default)]
+    pub token_type: Option<String>,
+    /// Lifetime in seconds.
+    #[serde(default)]
+    pub expires_in: Option<u64>,
+}
+
+impl ClientCredentialsGrant {
+    /// Exchanges a client id and secret for an access token.
+    ///
+    /// The client authenticates with HTTP basic auth, which every compliant server accepts.
+    pub async fn request_token(
+        &self,
+        http: &HttpClient,
+        client_id: &str,
+        client_secret: &str,
+    ) -> Result<AccessToken, ApiError> {
+        let mut form = vec![("grant_type", "client_credentials")];
+        if let Some(scope) = &self.scope {
+            form.push(("scope", scope));
+        }
+        let auth = AuthContext::basic(client_id, client_secret);
+        let response = http
+            .post_form::<AccessToken>(self.token_url.as_str(), &form, Some(&auth))
+            .await?;
+        let token = response.data;
+        match token.token_type.as_deref() {
+            None => Ok(token),
+            Some(kind) if kind.eq_ignore_ascii_case("bearer") => Ok(token),
+            Some(kind) => Err(ApiError::OAuth(format!(
+                "unsupported token_type '{kind}' (expected Bearer)"
+            ))),
+        }
+    }
+
+    /// Bearer auth for `key`: the cached access token, or a new one exchanged for the stored
+    /// client credentials through `endpoint`.
+    ///
+    /// The stored id and secret are only ever sent to the token endpoint, never returned as
+    /// auth for the service itself.
+    pub async fn authorize(
+        &self,
+        credentials: &CredentialManager,
+        key: &CredentialKey,
+        endpoint: ApiClientConfig,
+    ) -> Result<AuthContext, ApiError> {
+        if let Some(token) = credentials.cached_token(key) {
+            return Ok(AuthContext::bearer(token));
+        }
+        let secret = credentials
+            .get_credentials(key)?
+            .ok_or_else(|| AuthError::MissingCredentials(key.to_string()))?;
+        let http = HttpClient::new(endpoint)?;
+        let token = self
+            .request_token(&http, &secret.username, &secret.password)
+            .await?;
+        credentials.cache_token(key.clone(), token.to_cached());
+        Ok(AuthContext::bearer(token.access_token))
+    }
+}
+
+impl AccessToken {
+    /// Cache entry that expires shortly before the token does, so it is replaced in time.
+    pub fn to_cached(&self) -> CachedToken {
+        let ttl = self.expires_in.map(|secs| {
+            let lifetime = Duration::from_secs(secs);
+            lifetime - REFRESH_MARGIN.min(lifetime / 10)
+        });
+        CachedToken::new(self.access_token.clone(), ttl)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::auth::tests::MemoryStore;
+    use crate::services::auth::{CredentialDomain, CredentialSecret};
+    use httpmock::prelude::*;
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    #[tokio::test]
+    async fn exchanges_client_credentials_for_a_token() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/oauth2/token")
+                .header("authorization", "Basic bGVuczpzM2NyM3Q=")
+                .header("content-type", "application/x-www-form-urlencoded")
+                .body("grant_type=client_credentials&scope=fabrex.read");
+            then.status(200).json_body(serde_json::json!({
+                "access_token": "eyJ0b2tlbiI",
+                "token_type": "Bearer",
+                "expires_in": 3600
+            }));
+        });
+
+        let grant = ClientCredentialsGrant {
+            token_url: Url::parse(&server.url("/oauth2/token")).unwrap(),
+            scope: Some("fabrex.read".into()),
+        };
+        let http = HttpClient::new(ApiClientConfig::new(grant.token_url.clone())).unwrap();
+        let token = grant.request_token(&http, "lens", "s3cr3t").await.unwrap();
+        mock.assert();
+        assert_eq!(token.access_token, "eyJ0b2tlbiI");
+
+        // Refreshed a minute early for an hour-long token.
+        let expires_at = token.to_cached().expires_at.unwrap();
+        let ttl = expires_at - Instant::now();
+        assert!(ttl > Duration::from_secs(3530) && ttl <= Duration::from_secs(3540));
+    }
+
+    #[tokio::test]
+    async fn rejects_tokens_that_are_not_bearer_tokens() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(POST).path("/oauth2/token");
+            then.status(200).json_body(serde_json::json!({
+                "access_token": "mac-key",
+                "token_type": "mac"
+            }));
+        });
+
+        let grant = ClientCredentialsGrant {
+            token_url: Url::parse(&server.url("/oauth2/token")).unwrap(),
+            scope: None,
+        };
+        let http = HttpClient::new(ApiClientConfig::new(grant.token_url.clone())).unwrap();
+        let err = grant
+            .request_token(&http, "lens", "s3cr3t")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ApiError::OAuth(message) if message.contains("'mac'")));
+    }
+
+    #[test]
+    fn short_lived_tokens_refresh_after_most_of_their_lifetime() {
+        let token = AccessToken {
+            access_token: "t".into(),
+            token_type: None,
+            expires_in: Some(100),
+        };
+        let ttl = token.to_cached().expires_at.unwrap() - Instant::now();
+        assert!(ttl > Duration::from_secs(89) && ttl <= Duration::from_secs(90));
+    }
+
+    #[tokio::test]
+    async fn authorize_uses_the_exchanged_token_even_if_it_expires_at_once() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/oauth2/token");
+            then.status(200).json_body(serde_json::json!({
+                "access_token": "short-lived",
+                "expires_in": 0
+            }));
+        });
+
+        let credentials = CredentialManager::new(Arc::new(MemoryStore::new()));
+        let key = CredentialKey::default(CredentialDomain::FabreX);
+        let secret = CredentialSecret {
+            username: "lens".into(),
+            password: "s3cr3t".into(),
+            api_token: None,
+        };
+        credentials.set_credentials(&key, &secret).unwrap();
+
+        let grant = ClientCredentialsGrant {
+            token_url: Url::parse(&server.url("/oauth2/token")).unwrap(),
+            scope: None,
+        };
+        let endpoint = ApiClientConfig::new(grant.token_url.clone());
+        for _ in 0..2 {
+            let auth = grant
+                .authorize(&credentials, &key, endpoint.clone())
+                .await
+                .unwrap();
+            assert_eq!(auth.bearer_token.as_deref(), Some("short-lived"));
+            assert!(auth.basic.is_none());
+        }
+        // Nothing was cached, so each call exchanged the client credentials again.
+        mock.assert_hits(2);
+    }
+}
@@ -0,0 +1,176 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Typed fields decoded from an error response body.
+///
+/// Recognises RFC 7807 `application/problem+json` documents, Redfish `error` objects with
+/// `@Message.ExtendedInfo`, and the plain `{"code", "message"}` bodies some vendor APIs return.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorDetails {
+    /// Problem `type` URI, Redfish error code or vendor error code.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Redfish registry message id, e.g. `Base.1.8.ResourceNotFound`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<String>,
+    /// Suggested corrective action, when the service provides one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<String>,
+}
+
+impl ErrorDetails {
+    /// Decodes `body`, returning `None` when it carries no recognisable error fields.
+    pub fn parse(content_type: Option<&str>, body: &str) -> Option<Self> {
+        let Ok(Value::Object(object)) = serde_json::from_str::<Value>(body) else {
+            return None;
+        };
+
+        let is_problem = content_type
+            .map(|value| value.to_ascii_lowercase().contains("problem+json"))
+            .unwrap_or(false);
+        let details = if is_problem {
+            Self::from_problem(&object)
+        } else if let Some(Value::Object(error)) = object.get("error") {
+            Self::from_redfish(error)
+        } else if object.contains_key("title") || object.contains_key("detail") {
+            Self::from_problem(&object)
+        } else {
+            Self::from_vendor(&object)
+        };
+
+        Some(details).filter(|details| details.summary().is_some())
+    }
+
+    /// One-line description suitable for the status bar or a CLI error.
+    pub fn summary(&self) -> Option<String> {
+        // Registry messages are self-contained; the top-level Redfish message is usually a
+        // generic "see ExtendedInfo" pointer and is left out.
+        let mut summary = match (&self.title, &self.detail) {
+            (Some(title), Some(detail)) if title != detail && self.message_id.is_none() => {
+                format!("{title}: {detail}")
+            }
+            (_, Some(text)) | (Some(text), None) => text.clone(),
+            (None, None) => return None,
+        };
+        if let Some(message_id) = &self.message_id {
+            summary.push_str(&format!(" [{message_id}]"));
+        }
+        if let Some(resolution) = &self.resolution {
+            summary.push_str(&format!(" Resolution: {resolution}"));
+        }
+        Some(summary)
+    }
+
+    fn from_problem(object: &Map<String, Value>) -> Self {
+        Self {
+            code: text(object, "type").filter(|kind| kind != "about:blank"),
+            title: text(object, "title"),
+            detail: text(object, "detail"),
+            message_id: None,
+            resolution: None,
+        }
+    }
+
+    fn from_redfish(error: &Map<String, Value>) -> Self {
+        let extended = error
+            .get("@Message.ExtendedInfo")
+            .and_then(Value::as_array)
+            .and_then(|entries| entries.first())
+            .and_then(Value::as_object);
+
+        Self {
+            code: text(error, "code"),
+            title: text(error, "message"),
+            detail: extended.and_then(|info| text(info, "Message")),
+            message_id: extended.and_then(|info| text(info, "MessageId")),
+            resolution: extended.and_then(|info| text(info, "Resolution")),
+        }
+    }
+
+    fn from_vendor(object: &Map<String, Value>) -> Self {
+        Self {
+            code: text(object, "code").or_else(|| text(object, "error")),
+            title: None,
+            detail: text(object, "message").or_else(|| text(object, "error_description")),
+            message_id: None,
+            resolution: None,
+        }
+    }
+}
+
+fn text(object: &Map<String, Value>, key: &str) -> Option<String> {
+    match object.get(key)? {
+        Value::String(value) if !value.trim().is_empty() => Some(value.trim().to_string()),
+        Value::Number(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_problem_json() {
+        let body = r#"{
+            "type": "https://api.gigaio.com/problems/endpoint-busy",
+            "title": "Endpoint busy",
+            "status": 409,
+            "detail": "Endpoint ep-9 is attached to a running workload"
+        }"#;
+
+        let details = ErrorDetails::parse(Some("application/problem+json"), body).unwrap();
+        assert_eq!(
+            details.code.as_deref(),
+            Some("https://api.gigaio.com/problems/endpoint-busy")
+        );
+        assert_eq!(
+            details.summary().unwrap(),
+            "Endpoint busy: Endpoint ep-9 is attached to a running workload"
+        );
+    }
+
+    #[test]
+    fn parses_redfish_extended_info() {
+        let body = r#"{
+            "error": {
+                "code": "Base.1.8.GeneralError",
+                "message": "A general error has occurred. See ExtendedInfo for more information.",
+                "@Message.ExtendedInfo": [{
+                    "MessageId": "Base.1.8.ResourceNotFound",
+                    "Message": "The requested resource of type Session named 42 was not found.",
+                    "Resolution": "Provide a valid resource identifier and resubmit the request."
+                }]
+            }
+        }"#;
+
+        let details = ErrorDetails::parse(Some("application/json"), body).unwrap();
+        assert_eq!(details.code.as_deref(), Some("Base.1.8.GeneralError"));
+        assert_eq!(
+            details.message_id.as_deref(),
+            Some("Base.1.8.ResourceNotFound")
+        );
+        assert_eq!(
+            details.summary().unwrap(),
+            "The requested resource of type Session named 42 was not found. \
+             [Base.1.8.ResourceNotFound] Resolution: Provide a valid resource identifier and \
+             resubmit the request."
+        );
+    }
+
+    #[test]
+    fn parses_vendor_bodies_and_ignores_unstructured_ones() {
+        let details =
+            ErrorDetails::parse(None, r#"{"code": "E1042", "message": "quota exceeded"}"#).unwrap();
+        assert_eq!(details.code.as_deref(), Some("E1042"));
+        assert_eq!(details.summary().unwrap(), "quota exceeded");
+
+        assert_eq!(ErrorDetails::parse(None, "<html>Bad Gateway</html>"), None);
+        assert_eq!(ErrorDetails::parse(None, r#"{"items": []}"#), None);
+    }
+}
@@ -0,0 +1,147 @@
+use super::http::ApiError;
+use crate::services::auth::SecretSource;
+use reqwest::{ClientBuilder, NoProxy, Proxy};
+use std::fmt;
+use url::Url;
+
+/// How requests reach a service's base URL.
+#[derive(Debug, Clone, Default)]
+pub enum ProxyMode {
+    /// Honour the `HTTP_PROXY`/`HTTPS_PROXY`/`NO_PROXY` environment variables.
+    #[default]
+    System,
+    /// Connect directly, ignoring any proxy environment variables.
+    Direct,
+    /// Send every request through the given proxy.
+    Proxy(ProxyOptions),
+}
+
+/// An HTTP, HTTPS or SOCKS5 proxy and the hosts that bypass it.
+#[derive(Clone)]
+pub struct ProxyOptions {
+    /// `http://`, `https://`, `socks5://` or `socks5h://` (DNS resolved by the proxy).
+    pub url: Url,
+    /// Falls back to the username stored with a keyring `password`.
+    pub username: Option<String>,
+    /// Read from the keyring when the client is built if given as a reference.
+    pub password: Option<SecretSource>,
+    /// Comma-separated hosts, domains (`.example.com`) or CIDR ranges reached directly.
+    pub no_proxy: Option<String>,
+}
+
+impl fmt::Debug for ProxyOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProxyOptions")
+            .field("url", &self.url.as_str())
+            .field("username", &self.username)
+            .field("password", &self.password)
+            .field("no_proxy", &self.no_proxy)
+            .finish()
+    }
+}
+
+impl ProxyOptions {
+    pub fn new(url: &str) -> Result<Self, ApiError> {
+        let url = Url::parse(url)
+            .map_err(|err| ApiError::Proxy(format!("invalid proxy URL '{url}': {err}")))?;
+        if !matches!(url.scheme(), "http" | "https" | "socks5" | "socks5h") {
+            return Err(ApiError::Proxy(format!(
+                "unsupported proxy scheme '{}' (expected http, https, socks5 or socks5h)",
+                url.scheme()
+            )));
+        }
+        Ok(Self {
+            url,
+            username: None,
+            password: None,
+            no_proxy: None,
+        })
+    }
+
+    fn to_reqwest(&self) -> Result<Proxy, ApiError> {
+        let mut proxy = Proxy::all(self.url.as_str())
+            .map_err(|err| ApiError::Proxy(format!("invalid proxy {}: {err}", self.url)))?;
+        let (stored_username, password) = match &self.password {
+            Some(secret) => secret.resolve()?,
+            None => (None, String::new()),
+        };
+        if let Some(username) = self.username.clone().or(stored_username) {
+            proxy = proxy.basic_auth(&username, &password);
+        }
+        let no_proxy = self.no_proxy.as_deref().and_then(NoProxy::from_string);
+        Ok(proxy.no_proxy(no_proxy))
+    }
+}
+
+impl ProxyMode {
+    pub(crate) fn apply(&self, builder: ClientBuilder) -> Result<ClientBuilder, ApiError> {
+        match self {
+            ProxyMode::System => Ok(builder),
+            ProxyMode::Direct => Ok(builder.no_proxy()),
+            ProxyMode::Proxy(options) => Ok(builder.proxy(options.to_reqwest()?)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::api::http::{ApiClientConfig, HttpClient};
+    use httpmock::prelude::*;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn rejects_unsupported_schemes() {
+        assert!(ProxyOptions::new("socks5h://jump.example.com:1080").is_ok());
+        assert!(matches!(
+            ProxyOptions::new("ftp://proxy.example.com"),
+            Err(ApiError::Proxy(_))
+        ));
+        assert!(matches!(
+            ProxyOptions::new("not a url"),
+            Err(ApiError::Proxy(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn sends_requests_through_http_proxy() {
+        let proxy = MockServer::start();
+        let mock = proxy.mock(|when, then| {
+            when.method(GET)
+                .path("/status")
+                .header("proxy-authorization", "Basic b3BzOnNlY3JldA==");
+            then.status(200).json_body(json!({ "ok": true }));
+        });
+
+        let mut options = ProxyOptions::new(&proxy.base_url()).unwrap();
+        options.username = Some("ops".into());
+        options.password = Some(SecretSource::Inline("secret".into()));
+        let config = ApiClientConfig::try_from_url("http://fabrex.invalid/")
+            .unwrap()
+            .with_proxy(ProxyMode::Proxy(options));
+        let client = HttpClient::new(config).unwrap();
+
+        let response = client.get_json::<Value>("/status", None).await.unwrap();
+        assert_eq!(response.data["ok"], true);
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn bypasses_proxy_for_no_proxy_hosts() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/status");
+            then.status(200).json_body(json!({ "ok": true }));
+        });
+
+        let mut options = ProxyOptions::new("http://proxy.invalid:3128").unwrap();
+        options.no_proxy = Some("localhost,127.0.0.1".into());
+        let config = ApiClientConfig::try_from_url(&server.base_url())
+            .unwrap()
+            .with_proxy(ProxyMode::Proxy(options));
+        let client = HttpClient::new(config).unwrap();
+
+        client.get_json::<Value>("/status", None).await.unwrap();
+        mock.assert();
+    }
+}
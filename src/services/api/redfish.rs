@@ -1,21 +1,50 @@
-use super::http::{ApiClientConfig, ApiError, HttpClient};
-use crate::services::auth::RedfishSession;
+use super::http::{ApiClientConfig, ApiError, AuthContext, HttpClient};
+use crate::services::auth::{AuthError, CredentialKey, CredentialManager, RedfishSession};
+use reqwest::header::LOCATION;
 use serde::Deserialize;
 use serde_json::json;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use url::Url;
 
 #[derive(Clone)]
 pub struct RedfishClient {
     http: Arc<HttpClient>,
+    auth: Option<AuthContext>,
 }
 
 impl RedfishClient {
     pub fn new(config: ApiClientConfig) -> Result<Self, ApiError> {
         Ok(Self {
             http: Arc::new(HttpClient::new(config)?),
+            auth: None,
         })
     }
 
+    pub fn with_auth(mut self, auth: AuthContext) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// The service root, which Redfish serves without authentication.
+    pub async fn service_root(&self) -> Result<RedfishServiceRoot, ApiError> {
+        let response = self
+            .http
+            .get_json::<RedfishServiceRoot>("/redfish/v1/", None)
+            .await?;
+        Ok(response.data)
+    }
+
+    pub async fn session_service(&self) -> Result<RedfishSessionService, ApiError> {
+        let response = self
+            .http
+            .get_json::<RedfishSessionService>("/redfish/v1/SessionService", self.auth.as_ref())
+            .await?;
+        Ok(response.data)
+    }
+
     pub async fn create_session(
         &self,
         username: &str,
@@ -37,12 +66,179 @@ impl RedfishClient {
             .and_then(|value| value.to_str().ok())
             .ok_or(ApiError::MissingAuthToken)?;
 
+        // Prefer the resource's own `@odata.id`, then `Location`, then the conventional path.
+        let odata_id = response
+            .data
+            .odata_id
+            .clone()
+            .or_else(|| {
+                let location = response.headers.get(LOCATION)?.to_str().ok()?;
+                Some(match Url::parse(location) {
+                    Ok(url) => url.path().to_string(),
+                    Err(_) => location.to_string(),
+                })
+            })
+            .unwrap_or_else(|| format!("/redfish/v1/SessionService/Sessions/{}", response.data.id));
+
         Ok(RedfishSession {
             session_id: response.data.id,
+            odata_id,
             auth_token: token.to_string(),
             expires_at: None,
         })
     }
+
+    /// Logs `session` out so it stops counting against the BMC's session limit.
+    pub async fn delete_session(&self, session: &RedfishSession) -> Result<(), ApiError> {
+        let auth = AuthContext::session(session.auth_token.clone());
+        self.http.delete(&session.odata_id, Some(&auth)).await
+    }
+}
+
+/// Keeps one Redfish session per BMC instead of logging in for every request.
+///
+/// The session token is cached through [`CredentialManager::cache_token`] with the expiry
+/// derived from the BMC's `SessionTimeout`. `SessionTimeout` is an idle timeout, so the expiry
+/// moves forward with every use; a session that sat idle for most of it is replaced (and the
+/// old one deleted) before the BMC can drop it. Call [`logout`](Self::logout) when done.
+pub struct RedfishSessionManager {
+    client: RedfishClient,
+    credentials: CredentialManager,
+    key: CredentialKey,
+    active: Mutex<Option<ActiveSession>>,
+}
+
+struct ActiveSession {
+    session: RedfishSession,
+    timeout: Option<Duration>,
+}
+
+impl ActiveSession {
+    /// Whether less than a fifth of the idle timeout is left.
+    fn is_due_for_refresh(&self) -> bool {
+        match (self.session.expires_at, self.timeout) {
+            (Some(expires_at), Some(timeout)) => Instant::now() + timeout / 5 >= expires_at,
+            _ => false,
+        }
+    }
+}
+
+impl RedfishSessionManager {
+    pub fn new(client: RedfishClient, credentials: CredentialManager, key: CredentialKey) -> Self {
+        Self {
+            client,
+            credentials,
+            key,
+            active: Mutex::new(None),
+        }
+    }
+
+    /// Auth for the current session, logging in first when there is no usable session.
+    pub async fn auth(&self) -> Result<AuthContext, ApiError> {
+        let mut active = self.active.lock().await;
+        if let Some(current) = active.as_mut() {
+            let cached = self.credentials.cached_token(&self.key).is_some();
+            if cached && !current.is_due_for_refresh() {
+                if let Some(timeout) = current.timeout {
+                    current.session.expires_at = Some(Instant::now() + timeout);
+                    self.cache(&current.session);
+                }
+                return Ok(AuthContext::session(current.session.auth_token.clone()));
+            }
+        }
+
+        let fresh = self.login().await?;
+        let auth = AuthContext::session(fresh.session.auth_token.clone());
+        if let Some(stale) = active.replace(fresh) {
+            if let Err(err) = self.client.delete_session(&stale.session).await {
+                tracing::warn!(%err, session = %stale.session.session_id, "Redfish logout failed");
+            }
+        }
+        Ok(auth)
+    }
+
+    /// Runs `request` with session auth; when the BMC answers `401` the session is re-created
+    /// and `request` runs once more.
+    pub async fn call<T, F, Fut>(&self, request: F) -> Result<T, ApiError>
+    where
+        F: Fn(AuthContext) -> Fut,
+        Fut: Future<Output = Result<T, ApiError>>,
+    {
+        match request(self.auth().await?).await {
+            Err(err) if err.is_unauthorized() => {
+                tracing::info!(key = %self.key, "Redfish session rejected; logging in again");
+                self.forget().await;
+                request(self.auth().await?).await
+            }
+            result => result,
+        }
+    }
+
+    /// Deletes the current session, if any.
+    pub async fn logout(&self) -> Result<(), ApiError> {
+        let session = self.active.lock().await.take();
+        self.credentials.evict_token(&self.key);
+        match session {
+            Some(active) => self.client.delete_session(&active.session).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Drops a session the BMC no longer accepts; there is nothing left to delete.
+    async fn forget(&self) {
+        self.active.lock().await.take();
+        self.credentials.evict_token(&self.key);
+    }
+
+    async fn login(&self) -> Result<ActiveSession, ApiError> {
+        let secret = self
+            .credentials
+            .get_credentials(&self.key)?
+            .ok_or_else(|| AuthError::MissingCredentials(self.key.to_string()))?;
+        let mut session = self
+            .client
+            .create_session(&secret.username, &secret.password)
+            .await?;
+        let created = Instant::now();
+
+        // Some BMCs restrict SessionService; without a timeout the session lives until a 401.
+        let timeout = match self
+            .client
+            .clone()
+            .with_auth(AuthContext::session(session.auth_token.clone()))
+            .session_service()
+            .await
+        {
+            Ok(service) => service.session_timeout.map(Duration::from_secs),
+            Err(err) => {
+                tracing::warn!(%err, "Could not read Redfish SessionTimeout");
+                None
+            }
+        };
+        session.expires_at = timeout.map(|timeout| created + timeout);
+        self.cache(&session);
+
+        Ok(ActiveSession { session, timeout })
+    }
+
+    fn cache(&self, session: &RedfishSession) {
+        self.credentials
+            .cache_token(self.key.clone(), session.clone().into_cached_token());
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct RedfishServiceRoot {
+    #[serde(default)]
+    pub redfish_version: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct RedfishSessionService {
+    #[serde(default)]
+    pub session_timeout: Option<u64>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -50,6 +246,8 @@ impl RedfishClient {
 struct RedfishSessionPayload {
     #[serde(rename = "Id")]
     pub id: String,
+    #[serde(rename = "@odata.id", default)]
+    pub odata_id: Option<String>,
     #[serde(default)]
     pub user_name: Option<String>,
 }
@@ -57,6 +255,8 @@ struct RedfishSessionPayload {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::services::auth::tests::MemoryStore;
+    use crate::services::auth::{CredentialDomain, CredentialSecret};
     use httpmock::prelude::*;
     use serde_json::json;
     use tokio;
@@ -84,5 +284,167 @@ mod tests {
         let session = client.create_session("admin", "secret").await.unwrap();
         assert_eq!(session.session_id, "session-1");
         assert_eq!(session.auth_token, "token123");
+        assert_eq!(
+            session.odata_id,
+            "/redfish/v1/SessionService/Sessions/session-1"
+        );
+    }
+
+    #[tokio::test]
+    async fn reads_the_service_root() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/redfish/v1/");
+            then.status(200).json_body(json!({
+                "Id": "RootService",
+                "RedfishVersion": "1.15.0"
+            }));
+        });
+
+        let config = ApiClientConfig::try_from_url(&server.url("/")).unwrap();
+        let root = RedfishClient::new(config)
+            .unwrap()
+            .service_root()
+            .await
+            .unwrap();
+        assert_eq!(root.redfish_version.as_deref(), Some("1.15.0"));
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn fetches_session_service() {
+        let server = MockServer::start();
+        let _mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/redfish/v1/SessionService")
+                .header_exists("authorization");
+            then.status(200).json_body(json!({
+                "Id": "SessionService",
+                "ServiceEnabled": true,
+                "SessionTimeout": 600
+            }));
+        });
+
+        let config = ApiClientConfig::try_from_url(&server.url("/")).unwrap();
+        let client = RedfishClient::new(config)
+            .unwrap()
+            .with_auth(AuthContext::basic("admin", "secret"));
+        let service = client.session_service().await.unwrap();
+        assert_eq!(service.session_timeout, Some(600));
+    }
+
+    fn bmc_credentials() -> (CredentialManager, CredentialKey) {
+        let credentials = CredentialManager::new(Arc::new(MemoryStore::new()));
+        let key = CredentialKey::new(CredentialDomain::Redfish, "bmc-1");
+        let secret = CredentialSecret {
+            username: "admin".into(),
+            password: "secret".into(),
+            api_token: None,
+        };
+        credentials.set_credentials(&key, &secret).unwrap();
+        (credentials, key)
+    }
+
+    fn session_mock<'a>(server: &'a MockServer, token: &str) -> httpmock::Mock<'a> {
+        server.mock(|when, then| {
+            when.method(POST).path("/redfish/v1/Sessions");
+            then.status(201)
+                .header("X-Auth-Token", token)
+                .json_body(json!({
+                    "@odata.id": format!("/redfish/v1/SessionService/Sessions/{token}"),
+                    "Id": token
+                }));
+        })
+    }
+
+    #[tokio::test]
+    async fn session_expiry_follows_session_timeout_and_logout_deletes_it() {
+        let server = MockServer::start();
+        let login = session_mock(&server, "s1");
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/redfish/v1/SessionService")
+                .header("x-auth-token", "s1");
+            then.status(200).json_body(json!({ "SessionTimeout": 600 }));
+        });
+        let logout = server.mock(|when, then| {
+            when.method(DELETE)
+                .path("/redfish/v1/SessionService/Sessions/s1")
+                .header("x-auth-token", "s1");
+            then.status(204);
+        });
+
+        let (credentials, key) = bmc_credentials();
+        let client =
+            RedfishClient::new(ApiClientConfig::try_from_url(&server.url("/")).unwrap()).unwrap();
+        let sessions = RedfishSessionManager::new(client, credentials.clone(), key.clone());
+
+        let auth = sessions.auth().await.unwrap();
+        assert_eq!(auth.session_token.as_deref(), Some("s1"));
+        sessions.auth().await.unwrap();
+        login.assert_hits(1);
+
+        let expires_at = sessions
+            .active
+            .lock()
+            .await
+            .as_ref()
+            .unwrap()
+            .session
+            .expires_at;
+        let remaining = expires_at.unwrap() - Instant::now();
+        assert!(remaining > Duration::from_secs(590) && remaining <= Duration::from_secs(600));
+        let cached = credentials.auth_context(&key).unwrap().unwrap();
+        assert_eq!(cached.session_token.as_deref(), Some("s1"));
+
+        sessions.logout().await.unwrap();
+        logout.assert();
+        assert!(credentials.cached_token(&key).is_none());
+    }
+
+    #[tokio::test]
+    async fn recreates_the_session_after_unauthorized() {
+        let server = MockServer::start();
+        let mut login = session_mock(&server, "s1");
+        let mut service = server.mock(|when, then| {
+            when.method(GET)
+                .path("/redfish/v1/SessionService")
+                .header("x-auth-token", "s1");
+            then.status(200).json_body(json!({ "SessionTimeout": 600 }));
+        });
+
+        let (credentials, key) = bmc_credentials();
+        let client =
+            RedfishClient::new(ApiClientConfig::try_from_url(&server.url("/")).unwrap()).unwrap();
+        let sessions = RedfishSessionManager::new(client.clone(), credentials, key);
+        sessions.auth().await.unwrap();
+
+        // The BMC drops the first session and only accepts a new one.
+        login.delete();
+        service.delete();
+        let relogin = session_mock(&server, "s2");
+        let rejected = server.mock(|when, then| {
+            when.method(GET)
+                .path("/redfish/v1/SessionService")
+                .header("x-auth-token", "s1");
+            then.status(401);
+        });
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/redfish/v1/SessionService")
+                .header("x-auth-token", "s2");
+            then.status(200).json_body(json!({ "SessionTimeout": 300 }));
+        });
+
+        let service = sessions
+            .call(|auth| {
+                let client = client.clone().with_auth(auth);
+                async move { client.session_service().await }
+            })
+            .await
+            .unwrap();
+        assert_eq!(service.session_timeout, Some(300));
+        rejected.assert_hits(1);
+        relogin.assert_hits(1);
     }
 }
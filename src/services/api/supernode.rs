@@ -1,5 +1,5 @@
 use super::http::{ApiClientConfig, ApiError, AuthContext, HttpClient, Paginated, Pagination};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::sync::Arc;
 
@@ -23,11 +23,7 @@ impl SupernodeClient {
     }
 
     pub async fn list_nodes(&self) -> Result<Vec<SupernodeNode>, ApiError> {
-        let response = self
-            .http
-            .get_json::<Paginated<SupernodeNode>>("/nodes", self.auth.as_ref())
-            .await?;
-        Ok(response.data.items)
+        self.http.get_all("/nodes", self.auth.as_ref()).await
     }
 
     pub async fn list_nodes_paginated(
@@ -70,7 +66,7 @@ impl SupernodeClient {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SupernodeNode {
     pub id: String,
@@ -79,7 +75,7 @@ pub struct SupernodeNode {
     pub status: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SupernodeHealth {
     pub node_id: String,
@@ -89,14 +85,14 @@ pub struct SupernodeHealth {
     pub issues: Vec<SupernodeIssue>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SupernodeIssue {
     pub severity: String,
     pub description: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SupernodeActionResponse {
     pub request_id: String,
@@ -0,0 +1,279 @@
+use super::http::ApiError;
+use crate::services::auth::SecretSource;
+use rustls::client::{ServerCertVerified, ServerCertVerifier, WebPkiVerifier};
+use rustls::{Certificate, ClientConfig, OwnedTrustAnchor, PrivateKey, RootCertStore, ServerName};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// Per-service TLS settings layered on top of the bundled webpki roots.
+#[derive(Debug, Clone, Default)]
+pub struct TlsOptions {
+    /// Extra PEM-encoded CA certificates to trust, e.g. an internal PKI root.
+    pub ca_bundle: Option<PathBuf>,
+    /// Certificate and key presented to services that require mutual TLS.
+    pub client_identity: Option<ClientIdentity>,
+    /// SHA-256 fingerprint of the expected server certificate.
+    ///
+    /// A matching certificate is trusted on its own, which suits self-signed BMCs.
+    pub pinned_sha256: Option<[u8; 32]>,
+    /// Accept any server certificate. Only meant for lab BMCs.
+    pub insecure_skip_verify: bool,
+}
+
+#[derive(Clone)]
+pub enum ClientIdentity {
+    Pem {
+        cert: PathBuf,
+        key: PathBuf,
+    },
+    Pkcs12 {
+        path: PathBuf,
+        /// Read from the keyring when the client is built if given as a reference.
+        password: SecretSource,
+    },
+}
+
+impl fmt::Debug for ClientIdentity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientIdentity::Pem { cert, key } => f
+                .debug_struct("Pem")
+                .field("cert", cert)
+                .field("key", key)
+                .finish(),
+            ClientIdentity::Pkcs12 { path, .. } => f
+                .debug_struct("Pkcs12")
+                .field("path", path)
+                .field("password", &"<redacted>")
+                .finish(),
+        }
+    }
+}
+
+impl TlsOptions {
+    /// Whether these options match reqwest's built-in TLS setup.
+    pub fn is_default(&self) -> bool {
+        self.ca_bundle.is_none()
+            && self.client_identity.is_none()
+            && self.pinned_sha256.is_none()
+            && !self.insecure_skip_verify
+    }
+
+    /// Parses a SHA-256 fingerprint written as hex, with or without `:` separators.
+    pub fn parse_fingerprint(value: &str) -> Result<[u8; 32], ApiError> {
+        let digits: String = value
+            .trim()
+            .trim_start_matches("sha256:")
+            .chars()
+            .filter(|c| *c != ':')
+            .collect();
+        let bytes = hex::decode(&digits)
+            .map_err(|err| ApiError::Tls(format!("invalid certificate fingerprint: {err}")))?;
+        bytes.try_into().map_err(|bytes: Vec<u8>| {
+            ApiError::Tls(format!(
+                "certificate fingerprint must be 32 bytes, got {}",
+                bytes.len()
+            ))
+        })
+    }
+
+    pub(crate) fn client_config(&self) -> Result<ClientConfig, ApiError> {
+        let mut roots = RootCertStore::empty();
+        roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|anchor| {
+            OwnedTrustAnchor::from_subject_spki_name_constraints(
+                anchor.subject,
+                anchor.spki,
+                anchor.name_constraints,
+            )
+        }));
+        if let Some(path) = &self.ca_bundle {
+            let certs = read_certificates(path)?;
+            if certs.is_empty() {
+                return Err(ApiError::Tls(format!(
+                    "no certificates found in CA bundle {}",
+                    path.display()
+                )));
+            }
+            for cert in certs {
+                roots.add(&cert).map_err(|err| {
+                    ApiError::Tls(format!(
+                        "invalid CA certificate in {}: {err}",
+                        path.display()
+                    ))
+                })?;
+            }
+        }
+        let roots = Arc::new(roots);
+
+        let builder = ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots.clone());
+        let mut config = match &self.client_identity {
+            Some(identity) => {
+                let (chain, key) = identity.load()?;
+                builder
+                    .with_client_auth_cert(chain, key)
+                    .map_err(|err| ApiError::Tls(format!("invalid client certificate: {err}")))?
+            }
+            None => builder.with_no_client_auth(),
+        };
+
+        if self.pinned_sha256.is_some() || self.insecure_skip_verify {
+            config
+                .dangerous()
+                .set_certificate_verifier(Arc::new(ServerVerifier {
+                    webpki: WebPkiVerifier::new(roots, None),
+                    pinned_sha256: self.pinned_sha256,
+                    insecure_skip_verify: self.insecure_skip_verify,
+                }));
+        }
+
+        Ok(config)
+    }
+}
+
+impl ClientIdentity {
+    fn load(&self) -> Result<(Vec<Certificate>, PrivateKey), ApiError> {
+        match self {
+            ClientIdentity::Pem { cert, key } => {
+                let chain = read_certificates(cert)?;
+                if chain.is_empty() {
+                    return Err(ApiError::Tls(format!(
+                        "no certificates found in {}",
+                        cert.display()
+                    )));
+                }
+                Ok((chain, read_private_key(key)?))
+            }
+            ClientIdentity::Pkcs12 { path, password } => {
+                let (_, password) = password.resolve()?;
+                let data = std::fs::read(path).map_err(|err| read_error(path, err))?;
+                let store =
+                    p12_keystore::KeyStore::from_pkcs12(&data, &password).map_err(|err| {
+                        ApiError::Tls(format!("failed to open {}: {err}", path.display()))
+                    })?;
+                let (_, entry) = store.private_key_chain().ok_or_else(|| {
+                    ApiError::Tls(format!("no private key found in {}", path.display()))
+                })?;
+                let chain = entry
+                    .chain()
+                    .iter()
+                    .map(|cert| Certificate(cert.as_der().to_vec()))
+                    .collect();
+                Ok((chain, PrivateKey(entry.key().to_vec())))
+            }
+        }
+    }
+}
+
+/// Applies certificate pinning or, when explicitly requested, skips verification.
+struct ServerVerifier {
+    webpki: WebPkiVerifier,
+    pinned_sha256: Option<[u8; 32]>,
+    insecure_skip_verify: bool,
+}
+
+impl ServerCertVerifier for ServerVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &Certificate,
+        intermediates: &[Certificate],
+        server_name: &ServerName,
+        scts: &mut dyn Iterator<Item = &[u8]>,
+        ocsp_response: &[u8],
+        now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        if let Some(pinned) = &self.pinned_sha256 {
+            let actual: [u8; 32] = Sha256::digest(&end_entity.0).into();
+            if &actual != pinned {
+                return Err(rustls::Error::General(format!(
+                    "server certificate fingerprint {} does not match the pinned value",
+                    hex::encode(actual)
+                )));
+            }
+            return Ok(ServerCertVerified::assertion());
+        }
+        if self.insecure_skip_verify {
+            return Ok(ServerCertVerified::assertion());
+        }
+        self.webpki.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            scts,
+            ocsp_response,
+            now,
+        )
+    }
+}
+
+fn read_certificates(path: &Path) -> Result<Vec<Certificate>, ApiError> {
+    let file = File::open(path).map_err(|err| read_error(path, err))?;
+    let certs =
+        rustls_pemfile::certs(&mut BufReader::new(file)).map_err(|err| read_error(path, err))?;
+    Ok(certs.into_iter().map(Certificate).collect())
+}
+
+fn read_private_key(path: &Path) -> Result<PrivateKey, ApiError> {
+    let file = File::open(path).map_err(|err| read_error(path, err))?;
+    let items =
+        rustls_pemfile::read_all(&mut BufReader::new(file)).map_err(|err| read_error(path, err))?;
+    items
+        .into_iter()
+        .find_map(|item| match item {
+            rustls_pemfile::Item::PKCS8Key(key)
+            | rustls_pemfile::Item::RSAKey(key)
+            | rustls_pemfile::Item::ECKey(key) => Some(PrivateKey(key)),
+            _ => None,
+        })
+        .ok_or_else(|| ApiError::Tls(format!("no private key found in {}", path.display())))
+}
+
+fn read_error(path: &Path, err: std::io::Error) -> ApiError {
+    ApiError::Tls(format!("failed to read {}: {err}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_fingerprints_with_and_without_separators() {
+        let plain = "AB".repeat(32);
+        let colons = vec!["ab"; 32].join(":");
+        assert_eq!(TlsOptions::parse_fingerprint(&plain).unwrap(), [0xab; 32]);
+        assert_eq!(TlsOptions::parse_fingerprint(&colons).unwrap(), [0xab; 32]);
+        assert!(TlsOptions::parse_fingerprint("abcd").is_err());
+        assert!(TlsOptions::parse_fingerprint("not hex").is_err());
+    }
+
+    #[test]
+    fn rejects_ca_bundle_without_certificates() {
+        let path =
+            std::env::temp_dir().join(format!("fabrexlens-empty-ca-{}.pem", std::process::id()));
+        std::fs::write(&path, "not a certificate\n").unwrap();
+        let options = TlsOptions {
+            ca_bundle: Some(path.clone()),
+            ..TlsOptions::default()
+        };
+
+        let err = options.client_config().unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(err, ApiError::Tls(message) if message.contains("no certificates")));
+    }
+
+    #[test]
+    fn builds_insecure_config_without_files() {
+        let options = TlsOptions {
+            insecure_skip_verify: true,
+            ..TlsOptions::default()
+        };
+        assert!(!options.is_default());
+        assert!(options.client_config().is_ok());
+    }
+}
@@ -0,0 +1,241 @@
+use super::http::ApiError;
+use reqwest::header::HeaderMap;
+use reqwest::{Method, Request, Response};
+use serde_json::Value;
+use std::time::Duration;
+use url::Url;
+
+/// Target for all HTTP tracing events; request summaries are `DEBUG`, bodies are `TRACE`.
+pub const TARGET: &str = "fabrexlens::http";
+
+const REDACTED: &str = "<redacted>";
+const SENSITIVE_HEADERS: [&str; 5] = [
+    "authorization",
+    "proxy-authorization",
+    "x-auth-token",
+    "cookie",
+    "set-cookie",
+];
+const SENSITIVE_KEYS: [&str; 4] = ["password", "token", "secret", "authorization"];
+
+/// A sent request with credentials removed, kept for tracing and "copy as curl".
+#[derive(Debug, Clone)]
+pub struct RequestRecord {
+    pub method: Method,
+    pub url: Url,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+impl RequestRecord {
+    pub fn capture(request: &Request) -> Self {
+        let body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .map(|bytes| redact_body(&String::from_utf8_lossy(bytes)));
+        Self {
+            method: request.method().clone(),
+            url: redact_url(request.url()),
+            headers: redact_headers(request.headers()),
+            body,
+        }
+    }
+
+    /// A shell-ready `curl` command reproducing this request, secrets replaced by `<redacted>`.
+    pub fn to_curl(&self) -> String {
+        let mut command = format!("curl -X {} {}", self.method, shell_quote(self.url.as_str()));
+        for (name, value) in &self.headers {
+            command.push_str(&format!(" -H {}", shell_quote(&format!("{name}: {value}"))));
+        }
+        if let Some(body) = &self.body {
+            command.push_str(&format!(" --data-raw {}", shell_quote(body)));
+        }
+        command
+    }
+}
+
+/// Redacted `curl` command for the first API request in the chain that failed.
+pub fn failed_request_curl(err: &anyhow::Error) -> Option<String> {
+    err.chain()
+        .filter_map(|cause| cause.downcast_ref::<ApiError>())
+        .find_map(ApiError::request)
+        .map(|request| request.to_curl())
+}
+
+/// Records the outcome of one attempt; `queued` is the time spent waiting for request limits
+/// before it was sent. Bodies are only logged at `TRACE`.
+pub(crate) fn log_attempt(
+    record: &RequestRecord,
+    outcome: &Result<Response, reqwest::Error>,
+    latency: Duration,
+    queued: Duration,
+    attempt: u32,
+) {
+    let latency_ms = latency.as_millis() as u64;
+    let queue_ms = queued.as_millis() as u64;
+    match outcome {
+        Ok(response) => {
+            tracing::debug!(
+                target: TARGET,
+                method = %record.method,
+                url = %record.url,
+                status = response.status().as_u16(),
+                latency_ms,
+                queue_ms,
+                attempt,
+                "HTTP request"
+            );
+            tracing::trace!(
+                target: TARGET,
+                headers = ?record.headers,
+                body = record.body.as_deref().unwrap_or_default(),
+                response_headers = ?redact_headers(response.headers()),
+                "HTTP request details"
+            );
+            if !response.status().is_success() {
+                tracing::debug!(target: TARGET, curl = %record.to_curl(), "failed request");
+            }
+        }
+        Err(err) => {
+            tracing::debug!(
+                target: TARGET,
+                method = %record.method,
+                url = %record.url,
+                error = %err,
+                latency_ms,
+                queue_ms,
+                attempt,
+                "HTTP request failed"
+            );
+            tracing::debug!(target: TARGET, curl = %record.to_curl(), "failed request");
+        }
+    }
+}
+
+pub(crate) fn log_response_body(body: &str) {
+    if tracing::enabled!(target: TARGET, tracing::Level::TRACE) {
+        tracing::trace!(target: TARGET, body = %redact_body(body), "HTTP response body");
+    }
+}
+
+pub(crate) fn redact_headers(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if SENSITIVE_HEADERS.contains(&name.as_str()) {
+                REDACTED.to_string()
+            } else {
+                String::from_utf8_lossy(value.as_bytes()).into_owned()
+            };
+            (name.to_string(), value)
+        })
+        .collect()
+}
+
+fn is_sensitive(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    SENSITIVE_KEYS
+        .iter()
+        .any(|sensitive| key.contains(sensitive))
+}
+
+fn redact_url(url: &Url) -> Url {
+    let mut url = url.clone();
+    if url.password().is_some() {
+        let _ = url.set_password(Some(REDACTED));
+    }
+    if url.query_pairs().any(|(key, _)| is_sensitive(&key)) {
+        let pairs: Vec<(String, String)> = url
+            .query_pairs()
+            .map(|(key, value)| {
+                let value = if is_sensitive(&key) {
+                    REDACTED.to_string()
+                } else {
+                    value.into_owned()
+                };
+                (key.into_owned(), value)
+            })
+            .collect();
+        url.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+    url
+}
+
+/// Masks password, token and secret fields in JSON or form-encoded bodies.
+pub(crate) fn redact_body(body: &str) -> String {
+    if let Ok(mut value) = serde_json::from_str::<Value>(body) {
+        redact_value(&mut value);
+        return value.to_string();
+    }
+    if body.contains('=') && !body.contains(char::is_whitespace) {
+        return url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(
+                url::form_urlencoded::parse(body.as_bytes()).map(|(key, value)| {
+                    let value = if is_sensitive(&key) {
+                        REDACTED.into()
+                    } else {
+                        value
+                    };
+                    (key, value)
+                }),
+            )
+            .finish();
+    }
+    body.to_string()
+}
+
+fn redact_value(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, nested) in map.iter_mut() {
+                if is_sensitive(key) && !nested.is_null() {
+                    *nested = Value::String(REDACTED.into());
+                } else {
+                    redact_value(nested);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_value),
+        _ => {}
+    }
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::Client;
+
+    #[test]
+    fn redacts_credentials_in_curl_commands() {
+        let request = Client::new()
+            .post("https://bmc.example.com/redfish/v1/SessionService/Sessions?token=abc")
+            .header("X-Auth-Token", "session-token")
+            .basic_auth("admin", Some("hunter2"))
+            .json(&serde_json::json!({ "UserName": "admin", "Password": "hunter2" }))
+            .build()
+            .unwrap();
+
+        let curl = RequestRecord::capture(&request).to_curl();
+        assert!(!curl.contains("hunter2"));
+        assert!(!curl.contains("session-token"));
+        assert!(!curl.contains("abc"));
+        assert!(curl.starts_with(
+            "curl -X POST 'https://bmc.example.com/redfish/v1/SessionService/Sessions?token=%3Credacted%3E'"
+        ));
+        assert!(curl.contains("-H 'x-auth-token: <redacted>'"));
+        assert!(curl.contains(r#"--data-raw '{"Password":"<redacted>","UserName":"admin"}'"#));
+    }
+
+    #[test]
+    fn redacts_form_bodies_and_quotes_single_quotes() {
+        assert_eq!(
+            redact_body("grant_type=client_credentials&client_secret=s3cr3t"),
+            "grant_type=client_credentials&client_secret=%3Credacted%3E"
+        );
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+    }
+}
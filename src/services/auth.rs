@@ -4,11 +4,12 @@ use keyring::Entry;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
+use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use thiserror::Error;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum CredentialDomain {
     FabreX,
     Gryf,
@@ -16,6 +17,25 @@ pub enum CredentialDomain {
     Redfish,
 }
 
+impl CredentialDomain {
+    pub const ALL: [CredentialDomain; 4] = [
+        CredentialDomain::FabreX,
+        CredentialDomain::Gryf,
+        CredentialDomain::Supernode,
+        CredentialDomain::Redfish,
+    ];
+
+    /// Lowercase name used for per-service configuration tables.
+    pub fn config_key(&self) -> &'static str {
+        match self {
+            CredentialDomain::FabreX => "fabrex",
+            CredentialDomain::Gryf => "gryf",
+            CredentialDomain::Supernode => "supernode",
+            CredentialDomain::Redfish => "redfish",
+        }
+    }
+}
+
 impl fmt::Display for CredentialDomain {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -27,7 +47,21 @@ impl fmt::Display for CredentialDomain {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+impl FromStr for CredentialDomain {
+    type Err = AuthError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "fabrex" => Ok(CredentialDomain::FabreX),
+            "gryf" => Ok(CredentialDomain::Gryf),
+            "supernode" => Ok(CredentialDomain::Supernode),
+            "redfish" => Ok(CredentialDomain::Redfish),
+            _ => Err(AuthError::UnknownDomain(value.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct CredentialKey {
     domain: CredentialDomain,
     scope: String,
@@ -56,6 +90,11 @@ impl CredentialKey {
     fn storage_key(&self) -> String {
         format!("{}::{}", self.domain, self.scope)
     }
+
+    fn from_storage_key(value: &str) -> Option<Self> {
+        let (domain, scope) = value.split_once("::")?;
+        Some(Self::new(domain.parse().ok()?, scope))
+    }
 }
 
 impl fmt::Display for CredentialKey {
@@ -92,12 +131,80 @@ impl CredentialSecret {
     }
 }
 
+/// A password from the configuration: given inline, or a `keyring:<service>/<scope>` reference
+/// to credentials stored with `fabrexlens auth rotate <service> --scope <scope>`.
+#[derive(Clone, PartialEq, Eq)]
+pub enum SecretSource {
+    Inline(String),
+    Keyring(CredentialKey),
+}
+
+/// Prefix marking a configuration value as a keyring reference.
+const KEYRING_REFERENCE: &str = "keyring:";
+
+impl SecretSource {
+    pub fn parse(value: &str) -> Result<Self, AuthError> {
+        let Some(reference) = value.strip_prefix(KEYRING_REFERENCE) else {
+            return Ok(SecretSource::Inline(value.to_string()));
+        };
+        let (domain, scope) = reference
+            .split_once('/')
+            .filter(|(_, scope)| !scope.trim().is_empty())
+            .ok_or_else(|| {
+                AuthError::InvalidReference(format!(
+                    "'{value}' (expected keyring:<service>/<scope>)"
+                ))
+            })?;
+        Ok(SecretSource::Keyring(CredentialKey::new(
+            domain.parse()?,
+            scope.trim(),
+        )))
+    }
+
+    /// The password, with the stored username when it comes from the keyring.
+    pub fn resolve(&self) -> Result<(Option<String>, String), AuthError> {
+        self.resolve_in(&KeyringCredentialStore::new(KEYRING_SERVICE))
+    }
+
+    fn resolve_in(
+        &self,
+        store: &dyn CredentialStore,
+    ) -> Result<(Option<String>, String), AuthError> {
+        match self {
+            SecretSource::Inline(password) => Ok((None, password.clone())),
+            SecretSource::Keyring(key) => {
+                let secret = store
+                    .get(key)?
+                    .ok_or_else(|| AuthError::MissingCredentials(key.to_string()))?;
+                Ok((Some(secret.username), secret.password))
+            }
+        }
+    }
+}
+
+impl fmt::Debug for SecretSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SecretSource::Inline(_) => write!(f, "Inline(<redacted>)"),
+            SecretSource::Keyring(key) => write!(f, "Keyring({key})"),
+        }
+    }
+}
+
 pub trait CredentialStore: Send + Sync {
     fn save(&self, key: &CredentialKey, secret: &CredentialSecret) -> Result<(), AuthError>;
     fn get(&self, key: &CredentialKey) -> Result<Option<CredentialSecret>, AuthError>;
     fn delete(&self, key: &CredentialKey) -> Result<(), AuthError>;
+    /// Enumerates every key that currently has a stored secret.
+    fn list(&self) -> Result<Vec<CredentialKey>, AuthError>;
 }
 
+/// Keyring service every FabreXLens secret is stored under.
+const KEYRING_SERVICE: &str = "FabreXLens";
+
+/// Keyring account holding the list of stored keys, since OS keychains cannot be enumerated.
+const KEYRING_INDEX_ACCOUNT: &str = "__index__";
+
 pub struct KeyringCredentialStore {
     service_name: String,
 }
@@ -112,6 +219,29 @@ impl KeyringCredentialStore {
     fn entry(&self, key: &CredentialKey) -> Result<Entry, AuthError> {
         Entry::new(&self.service_name, &key.storage_key()).map_err(AuthError::Keyring)
     }
+
+    fn index_entry(&self) -> Result<Entry, AuthError> {
+        Entry::new(&self.service_name, KEYRING_INDEX_ACCOUNT).map_err(AuthError::Keyring)
+    }
+
+    fn read_index(&self) -> Result<Vec<String>, AuthError> {
+        match self.index_entry()?.get_password() {
+            Ok(payload) => Ok(serde_json::from_str(&payload)?),
+            Err(keyring::Error::NoEntry) => Ok(Vec::new()),
+            Err(err) => Err(AuthError::Keyring(err)),
+        }
+    }
+
+    fn update_index(&self, update: impl FnOnce(&mut Vec<String>)) -> Result<(), AuthError> {
+        let mut index = self.read_index()?;
+        update(&mut index);
+        index.sort();
+        index.dedup();
+        let payload = serde_json::to_string(&index)?;
+        self.index_entry()?
+            .set_password(&payload)
+            .map_err(AuthError::Keyring)
+    }
 }
 
 impl CredentialStore for KeyringCredentialStore {
@@ -119,7 +249,8 @@ impl CredentialStore for KeyringCredentialStore {
         let payload = serde_json::to_string(secret)?;
         self.entry(key)?
             .set_password(&payload)
-            .map_err(AuthError::Keyring)
+            .map_err(AuthError::Keyring)?;
+        self.update_index(|index| index.push(key.storage_key()))
     }
 
     fn get(&self, key: &CredentialKey) -> Result<Option<CredentialSecret>, AuthError> {
@@ -132,9 +263,30 @@ impl CredentialStore for KeyringCredentialStore {
 
     fn delete(&self, key: &CredentialKey) -> Result<(), AuthError> {
         match self.entry(key)?.delete_password() {
-            Ok(_) | Err(keyring::Error::NoEntry) => Ok(()),
-            Err(err) => Err(AuthError::Keyring(err)),
+            Ok(_) | Err(keyring::Error::NoEntry) => {}
+            Err(err) => return Err(AuthError::Keyring(err)),
         }
+        let storage_key = key.storage_key();
+        self.update_index(|index| index.retain(|entry| *entry != storage_key))
+    }
+
+    fn list(&self) -> Result<Vec<CredentialKey>, AuthError> {
+        let mut keys: Vec<CredentialKey> = self
+            .read_index()?
+            .iter()
+            .filter_map(|entry| CredentialKey::from_storage_key(entry))
+            .collect();
+
+        // Secrets stored before the index existed are only discoverable under their default scope.
+        for domain in CredentialDomain::ALL {
+            let key = CredentialKey::default(domain);
+            if !keys.contains(&key) && self.get(&key)?.is_some() {
+                keys.push(key);
+            }
+        }
+
+        keys.sort();
+        Ok(keys)
     }
 }
 
@@ -184,6 +336,12 @@ impl TokenCache {
         None
     }
 
+    pub fn remove(&self, key: &CredentialKey) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.remove(key);
+        }
+    }
+
     pub fn clear(&self) {
         if let Ok(mut inner) = self.inner.lock() {
             inner.clear();
@@ -194,6 +352,8 @@ impl TokenCache {
 #[derive(Debug, Clone)]
 pub struct RedfishSession {
     pub session_id: String,
+    /// Path of the session resource; deleting it logs the session out.
+    pub odata_id: String,
     pub auth_token: String,
     pub expires_at: Option<Instant>,
 }
@@ -231,7 +391,8 @@ impl CredentialManager {
     }
 
     pub fn with_default_keyring() -> Self {
-        let store: Arc<dyn CredentialStore> = Arc::new(KeyringCredentialStore::new("FabreXLens"));
+        let store: Arc<dyn CredentialStore> =
+            Arc::new(KeyringCredentialStore::new(KEYRING_SERVICE));
         Self::new(store)
     }
 
@@ -281,6 +442,10 @@ impl CredentialManager {
         self.token_cache.get(key)
     }
 
+    pub fn evict_token(&self, key: &CredentialKey) {
+        self.token_cache.remove(key);
+    }
+
     pub fn clear_cache(&self) {
         self.token_cache.clear();
     }
@@ -289,9 +454,23 @@ impl CredentialManager {
         self.store.get(key).map(|opt| opt.is_some())
     }
 
+    pub fn list_credentials(&self) -> Result<Vec<(CredentialKey, CredentialSecret)>, AuthError> {
+        let mut entries = Vec::new();
+        for key in self.store.list()? {
+            if let Some(secret) = self.store.get(&key)? {
+                entries.push((key, secret));
+            }
+        }
+        Ok(entries)
+    }
+
     pub fn auth_context(&self, key: &CredentialKey) -> Result<Option<AuthContext>, AuthError> {
+        // Redfish tokens belong to a session and travel in `X-Auth-Token`, not `Authorization`.
         if let Some(token) = self.cached_token(key) {
-            return Ok(Some(AuthContext::bearer(token)));
+            return Ok(Some(match key.domain() {
+                CredentialDomain::Redfish => AuthContext::session(token),
+                _ => AuthContext::bearer(token),
+            }));
         }
 
         match self.get_credentials(key)? {
@@ -351,18 +530,25 @@ pub enum AuthError {
     Prompt(#[from] dialoguer::Error),
     #[error("interactive prompts disabled; cannot create credentials for {0}")]
     InteractiveDisabled(String),
+    #[error("no credentials stored for {0}")]
+    MissingCredentials(String),
+    #[error("unknown credential domain: {0}")]
+    UnknownDomain(String),
+    #[error("invalid keyring reference {0}")]
+    InvalidReference(String),
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
 
-    struct MemoryStore {
+    /// In-memory [`CredentialStore`], shared with the tests of other modules.
+    pub(crate) struct MemoryStore {
         data: Mutex<HashMap<String, CredentialSecret>>,
     }
 
     impl MemoryStore {
-        fn new() -> Self {
+        pub(crate) fn new() -> Self {
             Self {
                 data: Mutex::new(HashMap::new()),
             }
@@ -386,6 +572,16 @@ mod tests {
             data.remove(&key.storage_key());
             Ok(())
         }
+
+        fn list(&self) -> Result<Vec<CredentialKey>, AuthError> {
+            let data = self.data.lock().unwrap();
+            let mut keys: Vec<CredentialKey> = data
+                .keys()
+                .filter_map(|entry| CredentialKey::from_storage_key(entry))
+                .collect();
+            keys.sort();
+            Ok(keys)
+        }
     }
 
     #[test]
@@ -446,4 +642,78 @@ mod tests {
         assert_eq!(ctx.bearer_token.as_deref(), Some("cached-token"));
         assert!(ctx.basic.is_none());
     }
+
+    #[test]
+    fn secret_sources_resolve_inline_values_and_keyring_references() {
+        let store = MemoryStore::new();
+        let key = CredentialKey::new(CredentialDomain::FabreX, "proxy");
+        let secret = CredentialSecret {
+            username: "ops".into(),
+            password: "s3cr3t".into(),
+            api_token: None,
+        };
+        store.save(&key, &secret).unwrap();
+
+        let reference = SecretSource::parse("keyring:fabrex/proxy").unwrap();
+        assert_eq!(reference, SecretSource::Keyring(key));
+        assert_eq!(
+            reference.resolve_in(&store).unwrap(),
+            (Some("ops".into()), "s3cr3t".into())
+        );
+        assert_eq!(
+            SecretSource::parse("changeit")
+                .unwrap()
+                .resolve_in(&store)
+                .unwrap(),
+            (None, "changeit".into())
+        );
+        assert!(!format!("{:?}", SecretSource::parse("changeit").unwrap()).contains("changeit"));
+
+        let missing = SecretSource::parse("keyring:gryf/proxy").unwrap();
+        assert!(matches!(
+            missing.resolve_in(&store),
+            Err(AuthError::MissingCredentials(_))
+        ));
+        assert!(matches!(
+            SecretSource::parse("keyring:fabrex"),
+            Err(AuthError::InvalidReference(_))
+        ));
+        assert!(matches!(
+            SecretSource::parse("keyring:bmc/tls"),
+            Err(AuthError::UnknownDomain(_))
+        ));
+    }
+
+    #[test]
+    fn storage_key_round_trips() {
+        let key = CredentialKey::new(CredentialDomain::Supernode, "lab::rack-2");
+        let parsed = CredentialKey::from_storage_key(&key.storage_key()).unwrap();
+        assert_eq!(parsed, key);
+        assert!(CredentialKey::from_storage_key("Unknown::default").is_none());
+    }
+
+    #[test]
+    fn lists_stored_credentials_in_key_order() {
+        let manager = CredentialManager::new(Arc::new(MemoryStore::new()));
+        let secret = CredentialSecret {
+            username: "user".into(),
+            password: "pass".into(),
+            api_token: None,
+        };
+        let redfish = CredentialKey::new(CredentialDomain::Redfish, "bmc-1");
+        let fabrex = CredentialKey::default(CredentialDomain::FabreX);
+        manager.set_credentials(&redfish, &secret).unwrap();
+        manager.set_credentials(&fabrex, &secret).unwrap();
+
+        let keys: Vec<CredentialKey> = manager
+            .list_credentials()
+            .unwrap()
+            .into_iter()
+            .map(|(key, _)| key)
+            .collect();
+        assert_eq!(keys, vec![fabrex.clone(), redfish]);
+
+        manager.delete_credentials(&fabrex).unwrap();
+        assert_eq!(manager.list_credentials().unwrap().len(), 1);
+    }
 }
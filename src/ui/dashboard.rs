@@ -1,10 +1,13 @@
 use crate::services::api::{
-    fabrex::UsageAlert, FabrexEndpoint, FabrexFabric, FabrexUsage, GryfWorkload, SupernodeNode,
+    fabrex::UsageAlert, FabrexEndpoint, FabrexFabric, FabrexUsage, GryfWorkload, RouteStats,
+    SupernodeNode, TransferStats,
 };
 use eframe::egui::{self, Color32, RichText, TextStyle};
+use serde::Serialize;
 use std::time::Instant;
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct DashboardSnapshot {
     pub fabrics: Vec<FabrexFabric>,
     pub fabric_usage: Vec<FabrexUsage>,
@@ -12,6 +15,10 @@ pub struct DashboardSnapshot {
     pub supernodes: Vec<SupernodeNode>,
     pub endpoints: Vec<FabrexEndpoint>,
     pub alerts: Vec<String>,
+    /// Requests and bytes spent producing this snapshot.
+    pub transfer: TransferStats,
+    /// Latency and error rate per backend route since the session started.
+    pub backend: Vec<RouteStats>,
 }
 
 #[derive(Debug)]
@@ -100,6 +107,8 @@ pub fn render(ui: &mut egui::Ui, state: &DashboardState) {
     render_workloads_section(ui, snapshot);
     ui.add_space(16.0);
     render_supernodes_section(ui, snapshot);
+    ui.add_space(16.0);
+    render_backend_section(ui, snapshot);
 
     if !snapshot.alerts.is_empty() {
         ui.add_space(16.0);
@@ -155,6 +164,17 @@ fn render_summary_cards(
             "Open notices",
             Color32::from_rgb(225, 85, 73),
         ),
+        SummaryCard::new(
+            "Network",
+            format!("{} requests", snapshot.transfer.requests),
+            &format!(
+                "{} received, {} not modified ({} saved)",
+                format_bytes(snapshot.transfer.bytes_received),
+                snapshot.transfer.not_modified,
+                format_bytes(snapshot.transfer.bytes_saved)
+            ),
+            Color32::from_rgb(0, 137, 150),
+        ),
         SummaryCard::new(
             "Last refresh",
             last_refresh
@@ -295,6 +315,43 @@ fn render_supernodes_section(ui: &mut egui::Ui, snapshot: &DashboardSnapshot) {
     });
 }
 
+fn render_backend_section(ui: &mut egui::Ui, snapshot: &DashboardSnapshot) {
+    section(ui, "Backend performance", |ui| {
+        if snapshot.backend.is_empty() {
+            ui.colored_label(Color32::GRAY, "No requests recorded yet.");
+            return;
+        }
+
+        egui::Grid::new("backend_grid")
+            .striped(true)
+            .spacing(egui::vec2(12.0, 8.0))
+            .show(ui, |ui| {
+                for heading in [
+                    "Service", "Route", "Requests", "p50", "p95", "Errors", "Received",
+                ] {
+                    ui.label(RichText::new(heading).strong());
+                }
+                ui.end_row();
+
+                for route in &snapshot.backend {
+                    ui.label(&route.service);
+                    ui.monospace(&route.route);
+                    ui.label(route.requests.to_string());
+                    ui.label(format!("{} ms", route.p50_ms));
+                    ui.colored_label(latency_color(route.p95_ms), format!("{} ms", route.p95_ms));
+                    let error_color = if route.error_rate > 0.0 {
+                        Color32::from_rgb(225, 85, 73)
+                    } else {
+                        ui.visuals().text_color()
+                    };
+                    ui.colored_label(error_color, format!("{:.1}%", route.error_rate * 100.0));
+                    ui.label(format_bytes(route.bytes));
+                    ui.end_row();
+                }
+            });
+    });
+}
+
 fn render_global_alerts(ui: &mut egui::Ui, snapshot: &DashboardSnapshot) {
     section(ui, "Alerts", |ui| {
         for entry in &snapshot.fabric_usage {
@@ -445,6 +502,16 @@ fn utilization_color(util_percent: f64) -> Color32 {
     }
 }
 
+fn latency_color(latency_ms: u64) -> Color32 {
+    if latency_ms >= 2000 {
+        Color32::from_rgb(225, 85, 73)
+    } else if latency_ms >= 500 {
+        Color32::from_rgb(236, 146, 36)
+    } else {
+        Color32::from_rgb(33, 150, 83)
+    }
+}
+
 fn average_utilization(snapshot: &DashboardSnapshot) -> Option<f64> {
     if snapshot.fabric_usage.is_empty() {
         None
@@ -460,6 +527,21 @@ fn average_utilization(snapshot: &DashboardSnapshot) -> Option<f64> {
     }
 }
 
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
 struct SummaryCard {
     title: String,
     value: String,